@@ -0,0 +1,90 @@
+//! Async mirror of `tlog_parser`, built on `mavlink::peek_reader::AsyncPeekReader` over
+//! an `embedded_io_async::Read` (or `tokio::io::AsyncRead` via its `embedded-io-async`
+//! compatibility shim). This lets a multi-gigabyte TLOG file be streamed without
+//! blocking an executor thread.
+
+use std::convert::TryInto;
+
+use embedded_io_async::Read as AsyncRead;
+use mavlink::error::MessageReadError;
+use mavlink::peek_reader::AsyncPeekReader;
+use mavlink::{
+    read_versioned_msg_async, MAVLinkV1MessageRaw, MAVLinkV2MessageRaw, MavlinkVersion, Message,
+};
+
+use crate::{AsyncMavParser, LogEntry};
+
+/// Async counterpart to `TlogParser`. See its documentation for the TLOG record
+/// layout and the timestamp-recovery/resync behavior this mirrors.
+///
+/// # Type Parameters
+/// - `R`: The async reader the log is streamed from.
+/// - `M`: The type of MAVLink message being parsed.
+pub struct AsyncTlogParser<R: AsyncRead, M: Message> {
+    reader: AsyncPeekReader<R>,
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<R: AsyncRead, M: Message> AsyncTlogParser<R, M> {
+    /// Creates a new `AsyncTlogParser` over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: AsyncPeekReader::new(reader),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<R: AsyncRead, M: Message> AsyncMavParser for AsyncTlogParser<R, M> {
+    type M = M;
+
+    /// Reads the next timestamped MAVLink record from the TLOG stream, mirroring
+    /// `TlogParser::next`.
+    async fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        let timestamp_raw = self.reader.read_exact(8).await?;
+        let mut timestamp_us = Some(u64::from_be_bytes(
+            timestamp_raw
+                .try_into()
+                .expect("read_exact(8) always returns exactly 8 bytes"),
+        ));
+
+        loop {
+            let start_byte = self.reader.peek_exact(1).await?[0];
+            let mav_version = match start_byte {
+                mavlink::MAV_STX => MavlinkVersion::V1,
+                mavlink::MAV_STX_V2 => MavlinkVersion::V2,
+                _ => {
+                    self.reader.read_u8().await?;
+                    timestamp_us = None;
+                    continue;
+                }
+            };
+
+            let (header, message) =
+                read_versioned_msg_async::<M, R>(&mut self.reader, mav_version).await?;
+
+            let raw = match mav_version {
+                MavlinkVersion::V1 => {
+                    let mut frame = MAVLinkV1MessageRaw::new();
+                    frame.serialize_message(header, &message);
+                    frame.raw_bytes().to_vec()
+                }
+                MavlinkVersion::V2 => {
+                    let mut frame = MAVLinkV2MessageRaw::new();
+                    frame.serialize_message(header, &message);
+                    frame.raw_bytes().to_vec()
+                }
+            };
+
+            return Ok(LogEntry {
+                timestamp: timestamp_us,
+                mav_header: Some(header),
+                mav_message: Some(message),
+                mav_version: Some(mav_version),
+                text: None,
+                raw: Some(raw),
+            });
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Combinator wrappers over `MavParser` that skip unwanted entries before a
+//! caller ever sees them, turning the "read everything in a loop and discard
+//! what you don't want" pattern (as in `test_tlog_parse`) into a composable
+//! query.
+//!
+//! `MessageIdFilter` and `QueryFilter` get a real short-circuit via
+//! `MavParser::peek_frame`/`skip_one`: a parser whose framing lets it learn a
+//! MAVLink frame's id/system id/component id before decoding its payload
+//! (`TlogParser` and the `mav_parser` sub-parsers do, since every frame
+//! carries its own length; `TimestampedMavlinkOnlyParser` and `MixedParser`
+//! only in `ParseMode::Lenient`) can reject a non-matching frame without paying for
+//! its per-field decode into `Self::M`. `peek_frame` defaults to `Ok(None)`,
+//! meaning "no cheaper way to check" -- both filters fall back to decoding via
+//! `next()` and matching on the result whenever that's what they get, so
+//! wrapping a parser that doesn't override it is still correct, just not
+//! faster. `TimeRangeFilter` and `Filter` have no such fast path (an arbitrary
+//! predicate can't be peeked), so they stay decode-then-match.
+
+use mavlink::error::MessageReadError;
+use mavlink::Message;
+
+use crate::{LogEntry, MavParser};
+
+/// The header fields of a MAVLink frame peekable from its wire bytes before
+/// its payload is decoded -- everything `MessageIdFilter`/`FilterSpec` can
+/// reject a non-matching entry on without paying for a full decode.
+#[derive(Clone, Copy, Debug)]
+pub struct PeekedFrame {
+    pub message_id: u32,
+    pub system_id: u8,
+    pub component_id: u8,
+}
+
+/// Wraps a `MavParser`, yielding only entries whose decoded MAVLink message id
+/// is in `ids`. Entries with no decoded `mav_message` (raw/text entries) are
+/// skipped.
+///
+/// Skips decoding a non-matching entry's body whenever the inner parser's
+/// `peek_frame`/`skip_one` support that -- see the module doc.
+pub struct MessageIdFilter<P: MavParser> {
+    inner: P,
+    ids: Vec<u32>,
+}
+
+impl<P: MavParser> MessageIdFilter<P> {
+    pub fn new(inner: P, ids: Vec<u32>) -> Self {
+        Self { inner, ids }
+    }
+}
+
+impl<P: MavParser> MavParser for MessageIdFilter<P> {
+    type M = P::M;
+
+    fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
+        loop {
+            if let Some(peeked) = self.inner.peek_frame()? {
+                if !self.ids.contains(&peeked.message_id) && self.inner.skip_one()? {
+                    continue;
+                }
+            }
+            let entry = self.inner.next()?;
+            if let Some(message) = &entry.mav_message {
+                if self.ids.contains(&message.message_id()) {
+                    return Ok(entry);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps a `MavParser`, yielding only entries timestamped within
+/// `[start_us, end_us]`. Entries with no timestamp are skipped.
+pub struct TimeRangeFilter<P: MavParser> {
+    inner: P,
+    start_us: u64,
+    end_us: u64,
+}
+
+impl<P: MavParser> TimeRangeFilter<P> {
+    pub fn new(inner: P, start_us: u64, end_us: u64) -> Self {
+        Self {
+            inner,
+            start_us,
+            end_us,
+        }
+    }
+}
+
+impl<P: MavParser> MavParser for TimeRangeFilter<P> {
+    type M = P::M;
+
+    fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
+        loop {
+            let entry = self.inner.next()?;
+            if let Some(timestamp) = entry.timestamp {
+                if timestamp >= self.start_us && timestamp <= self.end_us {
+                    return Ok(entry);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps a `MavParser`, yielding only entries for which `pred` returns `true`.
+pub struct Filter<P: MavParser, F: FnMut(&LogEntry<P::M>) -> bool> {
+    inner: P,
+    pred: F,
+}
+
+impl<P: MavParser, F: FnMut(&LogEntry<P::M>) -> bool> Filter<P, F> {
+    pub fn new(inner: P, pred: F) -> Self {
+        Self { inner, pred }
+    }
+}
+
+impl<P: MavParser, F: FnMut(&LogEntry<P::M>) -> bool> MavParser for Filter<P, F> {
+    type M = P::M;
+
+    fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
+        loop {
+            let entry = self.inner.next()?;
+            if (self.pred)(&entry) {
+                return Ok(entry);
+            }
+        }
+    }
+}
+
+/// Which of `LogEntry`'s payload kinds a `FilterSpec` should pass through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    Raw,
+    Mavlink,
+    Utf8Text,
+}
+
+/// A declarative query over a `MavParser` stream, combining the predicates
+/// `filter_message_ids`/`filter_time_range`/`filter` already expose individually
+/// into a single reusable value, so a caller doesn't have to hand-write a closure
+/// that re-checks `entry.mav_header`/`entry.mav_message` for every predicate it
+/// wants at once.
+///
+/// Every field defaults to `None`, meaning "don't constrain the match on this".
+/// An entry must satisfy every `Some` field to pass.
+#[derive(Clone, Debug, Default)]
+pub struct FilterSpec {
+    /// Decoded MAVLink message id must be one of these.
+    pub message_ids: Option<Vec<u32>>,
+    /// Decoded MAVLink header's `system_id` must be one of these.
+    pub system_ids: Option<Vec<u8>>,
+    /// Decoded MAVLink header's `component_id` must be one of these.
+    pub component_ids: Option<Vec<u8>>,
+    /// Timestamp must fall within `[start_us, end_us]`.
+    pub time_range: Option<(u64, u64)>,
+    /// Entry must be one of these payload kinds.
+    pub kinds: Option<Vec<EntryKind>>,
+}
+
+impl FilterSpec {
+    fn matches<M: Message>(&self, entry: &LogEntry<M>) -> bool {
+        if let Some(kinds) = &self.kinds {
+            let kind = if entry.mav_message.is_some() {
+                EntryKind::Mavlink
+            } else if entry.text.is_some() {
+                EntryKind::Utf8Text
+            } else if entry.raw.is_some() {
+                EntryKind::Raw
+            } else {
+                return false;
+            };
+            if !kinds.contains(&kind) {
+                return false;
+            }
+        }
+        if let Some(message_ids) = &self.message_ids {
+            match &entry.mav_message {
+                Some(message) if message_ids.contains(&message.message_id()) => {}
+                _ => return false,
+            }
+        }
+        if self.system_ids.is_some() || self.component_ids.is_some() {
+            match &entry.mav_header {
+                Some(header) => {
+                    if let Some(system_ids) = &self.system_ids {
+                        if !system_ids.contains(&header.system_id) {
+                            return false;
+                        }
+                    }
+                    if let Some(component_ids) = &self.component_ids {
+                        if !component_ids.contains(&header.component_id) {
+                            return false;
+                        }
+                    }
+                }
+                None => return false,
+            }
+        }
+        if let Some((start_us, end_us)) = self.time_range {
+            match entry.timestamp {
+                Some(timestamp) if timestamp >= start_us && timestamp <= end_us => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Whether `peeked` -- the subset of a frame's fields readable before its
+    /// payload is decoded -- already proves this spec would reject it.
+    ///
+    /// Returns `false` (definitely rejected, safe to skip without decoding) only
+    /// when a peeked id fails one of the spec's id constraints; otherwise
+    /// returns `true` ("might still match"), since `kinds`/`time_range` are not
+    /// decidable from a peek alone -- `QueryFilter` falls back to `matches` on the decoded
+    /// entry in that case, which is still correct, just not short-circuited.
+    fn matches_peeked(&self, peeked: &PeekedFrame) -> bool {
+        if let Some(message_ids) = &self.message_ids {
+            if !message_ids.contains(&peeked.message_id) {
+                return false;
+            }
+        }
+        if let Some(system_ids) = &self.system_ids {
+            if !system_ids.contains(&peeked.system_id) {
+                return false;
+            }
+        }
+        if let Some(component_ids) = &self.component_ids {
+            if !component_ids.contains(&peeked.component_id) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Wraps a `MavParser`, yielding only entries matching every predicate set on a
+/// `FilterSpec`.
+///
+/// Skips decoding a non-matching entry's body for the `message_ids`/
+/// `system_ids`/`component_ids` predicates whenever the inner parser's
+/// `peek_frame`/`skip_one` support that -- see the module doc. `kinds` and
+/// `time_range` can't be decided from a peek, so they're still only checked
+/// once an entry is fully decoded.
+pub struct QueryFilter<P: MavParser> {
+    inner: P,
+    spec: FilterSpec,
+}
+
+impl<P: MavParser> QueryFilter<P> {
+    pub fn new(inner: P, spec: FilterSpec) -> Self {
+        Self { inner, spec }
+    }
+}
+
+impl<P: MavParser> MavParser for QueryFilter<P> {
+    type M = P::M;
+
+    fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
+        loop {
+            if let Some(peeked) = self.inner.peek_frame()? {
+                if !self.spec.matches_peeked(&peeked) && self.inner.skip_one()? {
+                    continue;
+                }
+            }
+            let entry = self.inner.next()?;
+            if self.spec.matches(&entry) {
+                return Ok(entry);
+            }
+        }
+    }
+}
@@ -7,7 +7,7 @@
 use std::option::Option;
 
 use mavlink::error::MessageReadError;
-use mavlink::{MavHeader, Message};
+use mavlink::{MavHeader, MavlinkVersion, Message};
 
 #[cfg(feature = "MavLog")]
 /// Module for parsing MAVLink log files.
@@ -17,6 +17,19 @@ pub mod mav_parser;
 /// Module for parsing telemetry log (TLog) files.
 pub mod tlog_parser;
 
+#[cfg(all(feature = "Tlog", feature = "async"))]
+/// Async counterpart of `tlog_parser`, for streaming TLog files without blocking an
+/// executor.
+pub mod async_tlog_parser;
+
+#[cfg(feature = "Tlog")]
+/// Seekable, indexed random access (`LogIndex`) over TLOG files.
+pub mod log_index;
+
+/// Query combinators (`filter_message_ids`, `filter_time_range`, `filter`,
+/// `filter_messages`) for `MavParser`.
+pub mod filter;
+
 /// Represents a single log entry in a MAVLink log or telemetry log.
 ///
 /// # Type Parameters
@@ -26,12 +39,15 @@ pub mod tlog_parser;
 /// - `timestamp`: The timestamp of the log entry, if available.
 /// - `mav_header`: The MAVLink header associated with the message, if available.
 /// - `mav_message`: The MAVLink message, if available.
+/// - `mav_version`: The MAVLink protocol version `mav_header`/`mav_message` were
+///   decoded as, if available.
 /// - `text`: Any textual information associated with the log entry, if available.
 /// - `raw`: The raw binary data of the log entry, if available.
 pub struct LogEntry<M: Message> {
     pub timestamp: Option<u64>,
     pub mav_header: Option<MavHeader>,
     pub mav_message: Option<M>,
+    pub mav_version: Option<MavlinkVersion>,
     pub text: Option<String>,
     pub raw: Option<Vec<u8>>,
 }
@@ -45,6 +61,7 @@ impl<M: Message> Default for LogEntry<M> {
             timestamp: None,
             mav_header: None,
             mav_message: None,
+            mav_version: None,
             text: None,
             raw: None,
         }
@@ -71,4 +88,122 @@ pub trait MavParser {
     /// - `Ok(LogEntry<Self::M>)`: The next log entry if successfully read.
     /// - `Err(MessageReadError)`: An error if the log entry could not be read.
     fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError>;
+
+    /// Peeks the next entry's MAVLink message id, system id, and component id
+    /// without decoding its payload, if this parser's on-wire framing allows
+    /// it. Lets `filter_message_ids`/`filter_messages` reject a non-matching
+    /// frame before paying for its (often expensive) per-field decode into
+    /// `Self::M`.
+    ///
+    /// Defaults to `Ok(None)`, meaning "no cheaper way to check" -- callers
+    /// must fall back to `next()` and checking the decoded entry themselves in
+    /// that case, which is always correct, just not short-circuited. Never
+    /// consumes from the underlying source either way; use `skip_one` to
+    /// actually discard the peeked entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` on the same I/O failures `next` would.
+    fn peek_frame(&mut self) -> Result<Option<filter::PeekedFrame>, MessageReadError> {
+        Ok(None)
+    }
+
+    /// Discards exactly one entry without decoding it, for use after
+    /// `peek_frame` determines it should be skipped. Returns `Ok(false)` if
+    /// this parser has no cheaper-than-`next()` way to do that, in which case
+    /// the caller must fall back to `next()` (which does decode).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` on the same I/O failures `next` would.
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        Ok(false)
+    }
+
+    /// Wraps this parser so `next` only yields entries whose decoded MAVLink
+    /// message id is in `ids`, silently skipping everything else.
+    ///
+    /// Skips decoding a non-matching entry's body whenever this parser's
+    /// `peek_frame`/`skip_one` support it -- see `filter::MessageIdFilter`.
+    fn filter_message_ids(self, ids: &[u32]) -> filter::MessageIdFilter<Self>
+    where
+        Self: Sized,
+    {
+        filter::MessageIdFilter::new(self, ids.to_vec())
+    }
+
+    /// Wraps this parser so `next` only yields entries timestamped within
+    /// `[start_us, end_us]`, silently skipping everything else.
+    ///
+    /// See `filter::TimeRangeFilter` for caveats.
+    fn filter_time_range(self, start_us: u64, end_us: u64) -> filter::TimeRangeFilter<Self>
+    where
+        Self: Sized,
+    {
+        filter::TimeRangeFilter::new(self, start_us, end_us)
+    }
+
+    /// Wraps this parser so `next` only yields entries for which `pred`
+    /// returns `true`, silently skipping everything else.
+    fn filter<F: FnMut(&LogEntry<Self::M>) -> bool>(self, pred: F) -> filter::Filter<Self, F>
+    where
+        Self: Sized,
+    {
+        filter::Filter::new(self, pred)
+    }
+
+    /// Wraps this parser so `next` only yields entries matching every predicate
+    /// set on `spec`, silently skipping everything else.
+    ///
+    /// Like `filter_message_ids`, skips decoding a non-matching entry's body for
+    /// the `message_ids`/`system_ids`/`component_ids` predicates whenever this
+    /// parser's `peek_frame`/`skip_one` support it -- see `filter::QueryFilter`.
+    fn filter_messages(self, spec: filter::FilterSpec) -> filter::QueryFilter<Self>
+    where
+        Self: Sized,
+    {
+        filter::QueryFilter::new(self, spec)
+    }
+}
+
+/// Async counterpart to `MavParser`, for parsing a log without blocking an executor
+/// thread. Implemented with `async-trait` so it stays object-safe and can be boxed the
+/// same way the synchronous parsers are.
+///
+/// Lives at the crate root, alongside `MavParser`, so both the `mav_parser` and
+/// `tlog_parser` async implementations can share it without a feature-flag
+/// dependency between those otherwise-independent modules.
+#[cfg(feature = "async")]
+#[async_trait::async_trait(?Send)]
+pub trait AsyncMavParser {
+    type M: Message;
+
+    /// Reads the next log entry from the log source.
+    ///
+    /// # Returns
+    /// - `Ok(LogEntry<Self::M>)`: The next log entry if successfully read.
+    /// - `Err(MessageReadError)`: An error if the log entry could not be read.
+    async fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError>;
+
+    /// Turns this parser into a `Stream` of log entries, ending the stream (rather
+    /// than panicking or looping forever) at the first `Err`, which includes clean
+    /// EOF.
+    ///
+    /// Not part of the `async_trait`-generated object-safe interface: unlike `next`,
+    /// this consumes `self` and returns an `impl Stream`, so it's only callable on a
+    /// concrete, sized parser type.
+    fn into_stream(
+        self,
+    ) -> impl futures::Stream<Item = Result<LogEntry<Self::M>, MessageReadError>>
+    where
+        Self: Sized,
+    {
+        futures::stream::unfold(Some(self), |state| async move {
+            let mut parser = state?;
+            match parser.next().await {
+                Ok(entry) => Some((Ok(entry), Some(parser))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
 }
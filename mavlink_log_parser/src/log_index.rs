@@ -0,0 +1,177 @@
+//! Seekable, indexed random access over TLOG files.
+//!
+//! `TlogParser` only reads forward. For tools that need to jump straight to a
+//! timestamp or walk every occurrence of one message type (plotting or
+//! scrubbing a multi-gigabyte log, say), rescanning from the start every time
+//! is O(n) per lookup. `LogIndex::build` instead does a single forward pass,
+//! recording each record's byte offset, timestamp, system/component id, and
+//! message id, so `seek_to_time` and `iter_message_id` can jump straight to a
+//! byte offset in O(log n) and O(1) respectively.
+//!
+//! This needs a reader that can actually seek (`std::io::Seek`), which is why
+//! it's built on `TlogParser`'s native `File`-backed reader rather than the
+//! generic `Read`-only interface `MavLogParser` accepts for things like
+//! sockets or an opaque `mavlink::MavConnection`.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+
+use mavlink::error::MessageReadError;
+use mavlink::peek_reader::PeekReader;
+use mavlink::Message;
+
+use crate::tlog_parser::{read_tlog_record, TlogParser};
+use crate::{LogEntry, MavParser};
+
+/// One record's position and identifying fields, as recorded by `LogIndex::build`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexedEntry {
+    /// Byte offset of the start of this record (its 8-byte timestamp prefix).
+    pub offset: u64,
+    /// The record's timestamp, if its prefix wasn't discarded during resync.
+    pub timestamp: Option<u64>,
+    pub system_id: u8,
+    pub component_id: u8,
+    pub message_id: u32,
+}
+
+/// An index over a TLOG file built by a single forward pass, supporting a jump
+/// straight to a timestamp or to every occurrence of one message id, backed by
+/// a `TlogParser` that can be repositioned at any indexed offset.
+pub struct LogIndex<M: Message> {
+    file_path: String,
+    reader: TlogParser<M>,
+    /// All indexed records, in file order.
+    entries: Vec<IndexedEntry>,
+    /// Indices into `entries` of every record with a known timestamp, sorted
+    /// by that timestamp, for `seek_to_time`'s binary search.
+    by_time: Vec<(u64, usize)>,
+    /// Indices into `entries`, in file order, keyed by message id.
+    by_message_id: HashMap<u32, Vec<usize>>,
+}
+
+impl<M: Message> LogIndex<M> {
+    /// Builds a `LogIndex` for the TLOG file at `file_path` by reading and
+    /// discarding every record once, then leaves its reader positioned at the
+    /// start of the file, ready for `next()` or a `seek_to_time`/
+    /// `iter_message_id`-directed seek.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if the file could not be opened. Any error
+    /// while reading a record (end of file or otherwise) ends the pass, and the
+    /// records read before it make up the index.
+    pub fn build(file_path: &str) -> Result<Self, MessageReadError> {
+        let file = File::open(file_path).map_err(MessageReadError::Io)?;
+        let mut reader = PeekReader::new(BufReader::new(file));
+
+        let mut entries = Vec::new();
+        let mut by_time = Vec::new();
+        let mut by_message_id: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut offset: u64 = 0;
+
+        // Matches `test_tlog_parse`'s own loop: any error from reading the next record
+        // (clean EOF or otherwise) ends the pass.
+        while let Ok((entry, bytes_consumed)) = read_tlog_record::<M, BufReader<File>>(&mut reader, None) {
+            if let (Some(mav_header), Some(message)) = (entry.mav_header, &entry.mav_message) {
+                let index = entries.len();
+                if let Some(timestamp) = entry.timestamp {
+                    by_time.push((timestamp, index));
+                }
+                by_message_id
+                    .entry(message.message_id())
+                    .or_default()
+                    .push(index);
+                entries.push(IndexedEntry {
+                    offset,
+                    timestamp: entry.timestamp,
+                    system_id: mav_header.system_id,
+                    component_id: mav_header.component_id,
+                    message_id: message.message_id(),
+                });
+            }
+            offset += bytes_consumed;
+        }
+        by_time.sort_by_key(|(timestamp, _)| *timestamp);
+
+        Ok(Self {
+            file_path: file_path.to_string(),
+            reader: TlogParser::new(file_path),
+            entries,
+            by_time,
+            by_message_id,
+        })
+    }
+
+    /// All indexed records, in file order.
+    pub fn entries(&self) -> &[IndexedEntry] {
+        &self.entries
+    }
+
+    /// Repositions this index's reader at the first record timestamped at or
+    /// after `us`, so the next `next()` call returns that record.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if no indexed record is timestamped at or
+    /// after `us`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file can no longer be reopened or seeked (see `seek_to_offset`).
+    pub fn seek_to_time(&mut self, us: u64) -> Result<(), MessageReadError> {
+        let search_index = self.by_time.partition_point(|(timestamp, _)| *timestamp < us);
+        let (_, entry_index) = self.by_time.get(search_index).ok_or_else(|| {
+            MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("no indexed record timestamped at or after {us}us"),
+            ))
+        })?;
+        self.seek_to_offset(self.entries[*entry_index].offset)
+    }
+
+    /// Returns the byte offsets of every indexed record with message id `id`,
+    /// in file order.
+    pub fn iter_message_id(&self, id: u32) -> impl Iterator<Item = u64> + '_ {
+        self.by_message_id
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(move |&index| self.entries[index].offset)
+    }
+
+    /// Repositions this index's reader at `offset` bytes into the file.
+    ///
+    /// Always returns `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics (in `TlogParser::new_at_offset`) if the file could not be reopened
+    /// or seeked.
+    pub fn seek_to_offset(&mut self, offset: u64) -> Result<(), MessageReadError> {
+        self.reader = TlogParser::new_at_offset(&self.file_path, offset);
+        Ok(())
+    }
+}
+
+impl<M: Message> MavParser for LogIndex<M> {
+    type M = M;
+
+    /// Reads the next record from the current reader position, which starts
+    /// at the beginning of the file and can be moved with `seek_to_time` or
+    /// `seek_to_offset`.
+    fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
+        self.reader.next()
+    }
+
+    /// Delegates to the underlying `TlogParser`.
+    fn peek_frame(&mut self) -> Result<Option<crate::filter::PeekedFrame>, MessageReadError> {
+        self.reader.peek_frame()
+    }
+
+    /// Delegates to the underlying `TlogParser`.
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        self.reader.skip_one()
+    }
+}
@@ -0,0 +1,186 @@
+//! Async mirror of the synchronous parsers in `mav_parser`, built on
+//! `mavlink::peek_reader::AsyncPeekReader` over an `embedded_io_async::Read` (or
+//! `tokio::io::AsyncRead` via its `embedded-io-async` compatibility shim). This lets a
+//! live telemetry stream over an async socket or serial link be parsed as it arrives,
+//! without blocking a thread per connection.
+
+use std::convert::TryInto;
+
+use async_trait::async_trait;
+use embedded_io_async::Read as AsyncRead;
+use mavlink::error::MessageReadError;
+use mavlink::peek_reader::AsyncPeekReader;
+use mavlink::{read_versioned_msg_async, MavlinkVersion, Message};
+
+use super::EntryType;
+use crate::{AsyncMavParser, LogEntry};
+
+/// Async parser for MAVLink-only log files without timestamps.
+///
+/// See `MavlinkOnlyNoTimestampParser` for the synchronous equivalent.
+pub struct AsyncMavlinkOnlyNoTimestampParser<R: AsyncRead, M: Message> {
+    reader: AsyncPeekReader<R>,
+    mav_version: MavlinkVersion,
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<R: AsyncRead, M: Message> AsyncMavlinkOnlyNoTimestampParser<R, M> {
+    /// Creates a new parser over `reader`, which is assumed to contain only MAVLink
+    /// messages with no timestamps and no type byte.
+    pub fn new(reader: R, mav_version: MavlinkVersion) -> Self {
+        Self {
+            reader: AsyncPeekReader::new(reader),
+            mav_version,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<R: AsyncRead, M: Message> AsyncMavParser for AsyncMavlinkOnlyNoTimestampParser<R, M> {
+    type M = M;
+
+    /// Reads the next MAVLink message from the stream.
+    ///
+    /// If the data is corrupted, it will block and search for the next valid MAVLink packet,
+    /// mirroring `MavlinkOnlyNoTimestampParser::next`.
+    async fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        let mut entry: LogEntry<M> = LogEntry::default();
+        let (header, message) =
+            read_versioned_msg_async::<M, R>(&mut self.reader, self.mav_version).await?;
+        entry.mav_header = Some(header);
+        entry.mav_message = Some(message);
+        Ok(entry)
+    }
+}
+
+/// Async parser for MAVLink-only log files with timestamps.
+///
+/// See `TimestampedMavlinkOnlyParser` for the synchronous equivalent.
+pub struct AsyncTimestampedMavlinkOnlyParser<R: AsyncRead, M: Message> {
+    reader: AsyncPeekReader<R>,
+    mav_version: MavlinkVersion,
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<R: AsyncRead, M: Message> AsyncTimestampedMavlinkOnlyParser<R, M> {
+    /// Creates a new parser over `reader`, which is assumed to contain only MAVLink
+    /// messages, each preceded by an 8-byte little-endian timestamp.
+    pub fn new(reader: R, mav_version: MavlinkVersion) -> Self {
+        Self {
+            reader: AsyncPeekReader::new(reader),
+            mav_version,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<R: AsyncRead, M: Message> AsyncMavParser for AsyncTimestampedMavlinkOnlyParser<R, M> {
+    type M = M;
+
+    /// Reads the next MAVLink message and its timestamp from the stream.
+    ///
+    /// If the data is corrupted, it will silently fail and attempt to read the next
+    /// MAVLink message, mirroring `TimestampedMavlinkOnlyParser::next`.
+    async fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        let mut entry: LogEntry<M> = LogEntry::default();
+        let magic_number: u8 = match self.mav_version {
+            MavlinkVersion::V1 => mavlink::MAV_STX,
+            MavlinkVersion::V2 => mavlink::MAV_STX_V2,
+        };
+        if self.reader.peek_exact(9).await?[8] == magic_number {
+            let timestamp_raw: &[u8] = self.reader.read_exact(8).await?;
+            entry.timestamp = match timestamp_raw.try_into() {
+                Ok(bytes) => Some(u64::from_le_bytes(bytes)),
+                Err(_) => None,
+            };
+        }
+        // WARNING: this will silently fail and try to get next mavlink message on data corruption,
+        // matching the synchronous parser's behavior.
+        let (header, message) =
+            read_versioned_msg_async::<M, R>(&mut self.reader, self.mav_version).await?;
+        entry.mav_header = Some(header);
+        entry.mav_message = Some(message);
+        Ok(entry)
+    }
+}
+
+/// Async parser for mixed log streams containing various entry types.
+///
+/// See `MixedParser` for the synchronous equivalent.
+pub struct AsyncMixedParser<R: AsyncRead, M: Message> {
+    timestamped: bool,
+    reader: AsyncPeekReader<R>,
+    mav_version: MavlinkVersion,
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<R: AsyncRead, M: Message> AsyncMixedParser<R, M> {
+    /// Creates a new parser over `reader`, which may contain raw, text, and MAVLink
+    /// entries, optionally each preceded by an 8-byte little-endian timestamp.
+    pub fn new(reader: R, mav_version: MavlinkVersion, timestamped: bool) -> Self {
+        Self {
+            timestamped,
+            reader: AsyncPeekReader::new(reader),
+            mav_version,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<R: AsyncRead, M: Message> AsyncMavParser for AsyncMixedParser<R, M> {
+    type M = M;
+
+    /// Reads the next log entry from the stream, mirroring `MixedParser::next`.
+    async fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        let mut entry: LogEntry<M> = LogEntry::default();
+        let entry_type: EntryType = self
+            .reader
+            .read_u8()
+            .await
+            // If entry type is unknown default to raw
+            .map(|value| value.try_into().unwrap_or(EntryType::Raw))?;
+        if self.timestamped {
+            let timestamp_raw: &[u8] = self.reader.read_exact(8).await?;
+            entry.timestamp = match timestamp_raw.try_into() {
+                Ok(bytes) => Some(u64::from_le_bytes(bytes)),
+                Err(_) => None,
+            };
+        }
+        let payload_size: u16 = u16::from_le_bytes(
+            self.reader
+                .read_exact(2)
+                .await?
+                .try_into()
+                .expect("Failed to read log entry payload size."),
+        );
+        match entry_type {
+            EntryType::Raw => {
+                let payload = self.reader.read_exact(payload_size as usize).await?;
+                entry.raw = Some(payload.to_vec())
+            }
+            EntryType::Mavlink => {
+                let (header, message) =
+                    read_versioned_msg_async::<M, R>(&mut self.reader, self.mav_version).await?;
+                entry.mav_header = Some(header);
+                entry.mav_message = Some(message);
+                return Ok(entry);
+            }
+            EntryType::Utf8Text => {
+                let payload = self.reader.read_exact(payload_size as usize).await?;
+                entry.text = match String::from_utf8(payload.to_vec()) {
+                    Ok(text) => Some(text),
+                    Err(_) => {
+                        return Err(MessageReadError::Io(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "Failed to decode UTF-8 text from payload",
+                        )));
+                    }
+                };
+            }
+        }
+        Ok(entry)
+    }
+}
@@ -0,0 +1,411 @@
+//! Parses MAVLink dialect XML definitions embedded in, or linked from, a log
+//! file's header into an in-memory model, so logs that record their own dialect
+//! aren't limited to whatever `M: Message` was compiled into the reading binary.
+//!
+//! Each XML source's `<message>` elements are walked to build up field layouts.
+//! `<include>` references are not followed: only the sources named directly (an
+//! embedded XML blob, or each entry of a URL list) are loaded and parsed, and
+//! include targets are listed in `Dialect::unresolved_includes`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use super::header::{MavlinkDefinitionPayloadType, MavlinkMessageDefinition};
+
+/// A single field within a MAVLink message definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    /// The MAVLink wire type, e.g. `"uint8_t"` or `"char[16]"`.
+    pub field_type: String,
+    /// The element count, if `field_type` is an array type.
+    pub array_length: Option<usize>,
+    /// Whether this field was declared after the message's `<extensions/>`
+    /// marker. Extension fields are excluded from CRC_EXTRA, and are decoded
+    /// in declaration order rather than being reordered by size.
+    pub is_extension: bool,
+}
+
+impl FieldDef {
+    /// `field_type` with any trailing `[N]` array suffix stripped, e.g.
+    /// `"uint8_t[16]"` -> `"uint8_t"`.
+    pub(crate) fn base_type(&self) -> &str {
+        self.field_type
+            .find('[')
+            .map_or(self.field_type.as_str(), |open| &self.field_type[..open])
+    }
+
+    /// The size in bytes of one element of `base_type`, or `None` for an
+    /// unrecognized type.
+    pub(crate) fn base_type_size(&self) -> Option<usize> {
+        match self.base_type() {
+            "uint8_t" | "int8_t" | "char" | "uint8_t_mavlink_version" => Some(1),
+            "uint16_t" | "int16_t" => Some(2),
+            "uint32_t" | "int32_t" | "float" => Some(4),
+            "uint64_t" | "int64_t" | "double" => Some(8),
+            _ => None,
+        }
+    }
+}
+
+/// A single MAVLink message definition parsed from dialect XML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageDef {
+    pub id: u32,
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+    /// The CRC_EXTRA byte used to validate frames of this message, computed
+    /// from the message name and its non-extension fields' wire layout (see
+    /// `compute_crc_extra`).
+    pub crc_extra: Option<u8>,
+}
+
+impl MessageDef {
+    /// `fields` in the order they're laid out on the wire: non-extension
+    /// fields sorted by descending base-type size (ties keep their XML
+    /// declaration order, matching upstream mavgen's stable sort), followed
+    /// by extension fields in their original declaration order.
+    pub fn wire_order_fields(&self) -> Vec<&FieldDef> {
+        let (mut base, extensions): (Vec<&FieldDef>, Vec<&FieldDef>) =
+            self.fields.iter().partition(|field| !field.is_extension);
+        base.sort_by_key(|field| std::cmp::Reverse(field.base_type_size().unwrap_or(0)));
+        base.extend(extensions);
+        base
+    }
+}
+
+/// Accumulates one byte into a MAVLink X.25 CRC, per the reference
+/// implementation in the MAVLink C library's `checksum.h`.
+fn crc_accumulate(data: u8, crc: u16) -> u16 {
+    let mut tmp = data ^ (crc & 0xff) as u8;
+    tmp ^= tmp << 4;
+    (crc >> 8) ^ ((tmp as u16) << 8) ^ ((tmp as u16) << 3) ^ ((tmp as u16) >> 4)
+}
+
+fn crc_accumulate_str(s: &str, crc: u16) -> u16 {
+    s.bytes().fold(crc, |crc, byte| crc_accumulate(byte, crc))
+}
+
+/// `base_type` as pymavlink/mavgen spell it when hashing a field into
+/// CRC_EXTRA: the synthetic `uint8_t_mavlink_version` type (used only by
+/// `HEARTBEAT.mavlink_version`) is normalized to the `uint8_t` it's wire-
+/// compatible with before hashing, even though it's kept distinct from a
+/// plain `uint8_t` everywhere else (`FieldDef::base_type`/`base_type_size`).
+fn crc_type_name(base_type: &str) -> &str {
+    match base_type {
+        "uint8_t_mavlink_version" => "uint8_t",
+        other => other,
+    }
+}
+
+/// Computes the CRC_EXTRA byte used to validate frames of `def`: the X.25 CRC
+/// seeded at `0xFFFF`, accumulated over the message name and each
+/// non-extension field's type, name, and (for arrays) element count, then
+/// folded into a single byte.
+///
+/// Extension fields don't affect CRC_EXTRA, since older software compiled
+/// against a dialect without them must still be able to talk to a newer one
+/// that added them.
+pub fn compute_crc_extra(def: &MessageDef) -> u8 {
+    let mut crc: u16 = 0xFFFF;
+    crc = crc_accumulate_str(&format!("{} ", def.name), crc);
+    for field in def.wire_order_fields() {
+        if field.is_extension {
+            continue;
+        }
+        crc = crc_accumulate_str(&format!("{} ", crc_type_name(field.base_type())), crc);
+        crc = crc_accumulate_str(&format!("{} ", field.name), crc);
+        if let Some(array_length) = field.array_length {
+            crc = crc_accumulate(array_length as u8, crc);
+        }
+    }
+    ((crc & 0xFF) ^ (crc >> 8)) as u8
+}
+
+/// An in-memory MAVLink dialect resolved from one or more XML sources.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dialect {
+    /// Message definitions, keyed by message id.
+    pub messages: HashMap<u32, MessageDef>,
+    /// `<include>` targets named by the parsed XML. They are never loaded: every
+    /// include is listed here, even one whose file also appears in a URL list
+    /// and was merged into `messages` from there.
+    pub unresolved_includes: Vec<String>,
+}
+
+/// An error encountered while resolving or parsing a dialect definition.
+#[derive(Debug)]
+pub enum DialectError {
+    /// The XML could not be parsed.
+    Xml(quick_xml::Error),
+    /// A `<message>` element was missing a required `id` or `name` attribute,
+    /// or its `id` was not a valid integer.
+    MalformedMessage(String),
+    /// A linked URL used a scheme `DefaultDialectLoader` cannot load: anything
+    /// other than `file://` or a bare path, plus `http://`/`https://` when the
+    /// `fetch` feature is disabled.
+    UnsupportedUrlScheme(String),
+    /// A linked file could not be read from disk.
+    Io(std::io::Error),
+    /// A linked `http://`/`https://` URL could not be fetched.
+    #[cfg(feature = "fetch")]
+    Fetch(String),
+}
+
+impl fmt::Display for DialectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialectError::Xml(err) => write!(f, "failed to parse dialect XML: {err}"),
+            DialectError::MalformedMessage(msg) => write!(f, "malformed message definition: {msg}"),
+            DialectError::UnsupportedUrlScheme(url) => {
+                write!(f, "cannot resolve dialect URL (unsupported scheme): {url}")
+            }
+            DialectError::Io(err) => write!(f, "failed to read linked dialect file: {err}"),
+            #[cfg(feature = "fetch")]
+            DialectError::Fetch(err) => write!(f, "failed to fetch linked dialect URL: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DialectError {}
+
+/// Parses a single MAVLink dialect XML document into a `Dialect`.
+///
+/// `<include>` targets are recorded in `unresolved_includes` rather than
+/// followed, since a single XML blob has no other sources to resolve them
+/// against; see `resolve_from_urls` for multi-source resolution.
+pub fn parse_xml_dialect(xml: &str) -> Result<Dialect, DialectError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut dialect = Dialect::default();
+    let mut current_id: Option<u32> = None;
+    let mut current_name: Option<String> = None;
+    let mut current_fields: Vec<FieldDef> = Vec::new();
+    let mut in_message = false;
+    let mut in_extensions = false;
+    let mut in_include = false;
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf).map_err(DialectError::Xml)? {
+            Event::Eof => break,
+            Event::Start(tag) | Event::Empty(tag) => {
+                match tag.name().as_ref() {
+                    b"message" => {
+                        current_id = None;
+                        current_name = None;
+                        current_fields = Vec::new();
+                        in_message = true;
+                        in_extensions = false;
+                        for attr in tag.attributes().flatten() {
+                            let value = attr.unescape_value().unwrap_or_default().to_string();
+                            match attr.key.as_ref() {
+                                b"id" => {
+                                    current_id = Some(value.parse::<u32>().map_err(|_| {
+                                        DialectError::MalformedMessage(format!(
+                                            "non-numeric message id: {value}"
+                                        ))
+                                    })?);
+                                }
+                                b"name" => current_name = Some(value),
+                                _ => {}
+                            }
+                        }
+                    }
+                    b"field" => {
+                        let mut name = None;
+                        let mut field_type = None;
+                        for attr in tag.attributes().flatten() {
+                            let value = attr.unescape_value().unwrap_or_default().to_string();
+                            match attr.key.as_ref() {
+                                b"name" => name = Some(value),
+                                b"type" => field_type = Some(value),
+                                _ => {}
+                            }
+                        }
+                        if let (Some(name), Some(field_type)) = (name, field_type) {
+                            let array_length = field_type
+                                .find('[')
+                                .and_then(|open| {
+                                    field_type[open + 1..].find(']').map(|len| {
+                                        field_type[open + 1..open + 1 + len].to_string()
+                                    })
+                                })
+                                .and_then(|n| n.parse::<usize>().ok());
+                            current_fields.push(FieldDef {
+                                name,
+                                field_type,
+                                array_length,
+                                is_extension: in_extensions,
+                            });
+                        }
+                    }
+                    b"extensions" if in_message => in_extensions = true,
+                    b"include" => in_include = true,
+                    _ => {}
+                }
+            }
+            Event::Text(text) if in_include => {
+                let target = text.unescape().unwrap_or_default().trim().to_string();
+                if !target.is_empty() {
+                    dialect.unresolved_includes.push(target);
+                }
+            }
+            Event::End(tag) => match tag.name().as_ref() {
+                b"message" => {
+                    if let (Some(id), Some(name)) = (current_id.take(), current_name.take()) {
+                        let mut message = MessageDef {
+                            id,
+                            name,
+                            fields: std::mem::take(&mut current_fields),
+                            crc_extra: None,
+                        };
+                        message.crc_extra = Some(compute_crc_extra(&message));
+                        dialect.messages.insert(id, message);
+                    }
+                    in_message = false;
+                    in_extensions = false;
+                }
+                b"include" => in_include = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(dialect)
+}
+
+/// Fetches the XML a single dialect URL refers to.
+///
+/// Implemented as a trait, rather than a plain function, so offline/air-gapped
+/// users can plug in a local cache (or any other source) in place of
+/// `DefaultDialectLoader` without touching `MessageDefinitionResolver` itself.
+pub trait DialectLoader {
+    /// Returns the XML document `url` refers to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DialectError` if `url` uses an unsupported scheme or the
+    /// document could not be read.
+    fn load(&self, url: &str) -> Result<String, DialectError>;
+}
+
+/// The default `DialectLoader`: resolves `file://` URLs and bare filesystem paths
+/// directly, and, behind the `fetch` feature, fetches `http://`/`https://` URLs
+/// over the network with `ureq`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultDialectLoader;
+
+impl DialectLoader for DefaultDialectLoader {
+    fn load(&self, url: &str) -> Result<String, DialectError> {
+        if let Some(path) = url.strip_prefix("file://") {
+            return std::fs::read_to_string(path).map_err(DialectError::Io);
+        }
+
+        #[cfg(feature = "fetch")]
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return ureq::get(url)
+                .call()
+                .map_err(|err| DialectError::Fetch(err.to_string()))?
+                .into_string()
+                .map_err(|err| DialectError::Fetch(err.to_string()));
+        }
+
+        if url.contains("://") {
+            return Err(DialectError::UnsupportedUrlScheme(url.to_string()));
+        }
+        std::fs::read_to_string(url).map_err(DialectError::Io)
+    }
+}
+
+/// Resolves a `MavlinkMessageDefinition`'s payload into a `Dialect`, mapping
+/// message ids to their names and field layouts so a log can be decoded even
+/// when its dialect wasn't compiled into the reading binary.
+///
+/// Generic over a `DialectLoader` so the `Utf8SpaceDelimitedUrlsForXMLFiles`
+/// variant's sources can come from somewhere other than the local filesystem or
+/// network; defaults to `DefaultDialectLoader` for the common case.
+pub struct MessageDefinitionResolver<L: DialectLoader = DefaultDialectLoader> {
+    loader: L,
+}
+
+impl MessageDefinitionResolver<DefaultDialectLoader> {
+    /// Creates a resolver using `DefaultDialectLoader`.
+    pub fn new() -> Self {
+        Self {
+            loader: DefaultDialectLoader,
+        }
+    }
+}
+
+impl Default for MessageDefinitionResolver<DefaultDialectLoader> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: DialectLoader> MessageDefinitionResolver<L> {
+    /// Creates a resolver that fetches `Utf8SpaceDelimitedUrlsForXMLFiles` sources
+    /// through `loader` instead of the default filesystem/network behavior.
+    pub fn with_loader(loader: L) -> Self {
+        Self { loader }
+    }
+
+    /// Resolves `definition`'s payload into a `Dialect`.
+    ///
+    /// `MavlinkDefinitionPayloadType::None` resolves to an empty `Dialect`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DialectError` if the payload is missing, isn't valid UTF-8, or
+    /// fails to parse as dialect XML, or (for the URL-list variant) if a linked
+    /// source couldn't be loaded.
+    pub fn resolve(&self, definition: &MavlinkMessageDefinition) -> Result<Dialect, DialectError> {
+        match definition.payload_type {
+            MavlinkDefinitionPayloadType::None => Ok(Dialect::default()),
+            MavlinkDefinitionPayloadType::Utf8Xml => {
+                let xml = self.payload_as_str(definition)?;
+                parse_xml_dialect(xml)
+            }
+            MavlinkDefinitionPayloadType::Utf8SpaceDelimitedUrlsForXMLFiles => {
+                let urls = self.payload_as_str(definition)?;
+                self.resolve_from_urls(urls)
+            }
+        }
+    }
+
+    fn payload_as_str<'a>(
+        &self,
+        definition: &'a MavlinkMessageDefinition,
+    ) -> Result<&'a str, DialectError> {
+        let payload = definition
+            .payload
+            .as_deref()
+            .ok_or_else(|| DialectError::MalformedMessage("payload_type implies a payload".into()))?;
+        std::str::from_utf8(payload).map_err(|err| DialectError::MalformedMessage(err.to_string()))
+    }
+
+    /// Resolves a whitespace-delimited list of dialect XML sources into a single
+    /// merged `Dialect`, following the `Utf8SpaceDelimitedUrlsForXMLFiles` payload
+    /// format. When multiple sources define the same message id, the first
+    /// definition encountered wins, matching how an including dialect's own
+    /// messages take priority over its includes.
+    fn resolve_from_urls(&self, urls: &str) -> Result<Dialect, DialectError> {
+        let mut merged = Dialect::default();
+        for url in urls.split_whitespace() {
+            let xml = self.loader.load(url)?;
+            let source = parse_xml_dialect(&xml)?;
+            for (id, message) in source.messages {
+                merged.messages.entry(id).or_insert(message);
+            }
+            merged.unresolved_includes.extend(source.unresolved_includes);
+        }
+        Ok(merged)
+    }
+}
@@ -0,0 +1,195 @@
+//! Decodes MAVLink payloads against a dynamically parsed `Dialect`, for logs
+//! whose dialect isn't compiled into the reading binary as a `Message` impl.
+//!
+//! `Dialect::decode_frame` parses a raw MAVLink v1 or v2 frame directly off its
+//! header bytes rather than going through `mavlink::read_versioned_msg`, since
+//! that requires a `Message` impl that already knows the dialect ahead of time.
+//! The result is a self-describing `DynamicMessage` instead of a typed `M`.
+
+use super::dialect::{Dialect, DialectError, FieldDef, MessageDef};
+
+/// A single decoded field value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    UInt8(u8),
+    Int8(i8),
+    UInt16(u16),
+    Int16(i16),
+    UInt32(u32),
+    Int32(i32),
+    UInt64(u64),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    /// A `char[N]` array, decoded as a string truncated at the first NUL byte,
+    /// matching how MAVLink treats fixed-size char arrays as C strings.
+    String(String),
+    /// Any other array field, decoded element by element.
+    Array(Vec<Value>),
+}
+
+/// A MAVLink message decoded against a `Dialect` rather than a compiled
+/// `Message` impl, so its fields are named and typed by the definition instead
+/// of by Rust.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicMessage {
+    pub id: u32,
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+impl Dialect {
+    /// Decodes `payload` (a message's MAVLink payload bytes, without the frame
+    /// header, CRC, or signature) against this dialect's definition for
+    /// `message_id`.
+    ///
+    /// Payloads shorter than the definition expects are zero-padded rather
+    /// than rejected, matching MAVLink v2's trailing-zero-byte truncation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DialectError::MalformedMessage` if `message_id` has no
+    /// definition in this dialect.
+    pub fn decode_payload(
+        &self,
+        message_id: u32,
+        payload: &[u8],
+    ) -> Result<DynamicMessage, DialectError> {
+        let def = self.messages.get(&message_id).ok_or_else(|| {
+            DialectError::MalformedMessage(format!("no definition for message id {message_id}"))
+        })?;
+        Ok(decode_payload(def, payload))
+    }
+
+    /// Decodes a complete raw MAVLink v1 or v2 frame, the format `TlogParser`
+    /// and `MavLogWriter` round-trip through `LogEntry::raw`: parses the frame
+    /// header to find the message id and payload bounds, then decodes the
+    /// payload against this dialect.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DialectError::MalformedMessage` if `raw` is too short to
+    /// contain a full frame header, doesn't start with a recognized MAVLink
+    /// start byte, or names a message id this dialect has no definition for.
+    pub fn decode_frame(&self, raw: &[u8]) -> Result<DynamicMessage, DialectError> {
+        let (message_id, payload) = split_frame(raw)?;
+        self.decode_payload(message_id, payload)
+    }
+}
+
+/// Parses a raw MAVLink v1 or v2 frame's header far enough to recover its
+/// message id and the bounds of its payload.
+fn split_frame(raw: &[u8]) -> Result<(u32, &[u8]), DialectError> {
+    match raw.first() {
+        Some(&mavlink::MAV_STX) => {
+            // v1: STX(1) LEN(1) SEQ(1) SYSID(1) COMPID(1) MSGID(1) PAYLOAD(LEN) CRC(2)
+            if raw.len() < 6 {
+                return Err(DialectError::MalformedMessage(
+                    "v1 frame shorter than its 6-byte header".into(),
+                ));
+            }
+            let len = raw[1] as usize;
+            let message_id = raw[5] as u32;
+            let payload = raw.get(6..6 + len).ok_or_else(|| {
+                DialectError::MalformedMessage(
+                    "v1 frame shorter than its declared payload length".into(),
+                )
+            })?;
+            Ok((message_id, payload))
+        }
+        Some(&mavlink::MAV_STX_V2) => {
+            // v2: STX(1) LEN(1) INCOMPAT(1) COMPAT(1) SEQ(1) SYSID(1) COMPID(1)
+            //     MSGID(3, little-endian) PAYLOAD(LEN) CRC(2) [SIGNATURE(13)]
+            if raw.len() < 10 {
+                return Err(DialectError::MalformedMessage(
+                    "v2 frame shorter than its 10-byte header".into(),
+                ));
+            }
+            let len = raw[1] as usize;
+            let message_id = u32::from_le_bytes([raw[7], raw[8], raw[9], 0]);
+            let payload = raw.get(10..10 + len).ok_or_else(|| {
+                DialectError::MalformedMessage(
+                    "v2 frame shorter than its declared payload length".into(),
+                )
+            })?;
+            Ok((message_id, payload))
+        }
+        Some(other) => Err(DialectError::MalformedMessage(format!(
+            "unrecognized frame start byte 0x{other:02x}"
+        ))),
+        None => Err(DialectError::MalformedMessage("empty frame".into())),
+    }
+}
+
+/// Decodes `payload` field by field, in `def`'s wire order, zero-padding any
+/// bytes `payload` is too short to supply.
+fn decode_payload(def: &MessageDef, payload: &[u8]) -> DynamicMessage {
+    let mut fields = Vec::with_capacity(def.fields.len());
+    let mut offset = 0usize;
+    for field in def.wire_order_fields() {
+        let (value, consumed) = decode_field(field, payload, offset);
+        offset += consumed;
+        fields.push((field.name.clone(), value));
+    }
+    DynamicMessage {
+        id: def.id,
+        name: def.name.clone(),
+        fields,
+    }
+}
+
+/// Decodes one field starting at `offset` in `payload`, returning its value and
+/// the number of bytes it occupies.
+fn decode_field(field: &FieldDef, payload: &[u8], offset: usize) -> (Value, usize) {
+    let element_size = field.base_type_size().unwrap_or(1);
+    let element_count = field.array_length.unwrap_or(1);
+    let total_len = element_size * element_count;
+    let bytes = read_padded(payload, offset, total_len);
+
+    let value = if field.base_type() == "char" && field.array_length.is_some() {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Value::String(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    } else if field.array_length.is_some() {
+        Value::Array(
+            bytes
+                .chunks(element_size.max(1))
+                .map(|chunk| decode_scalar(field.base_type(), chunk))
+                .collect(),
+        )
+    } else {
+        decode_scalar(field.base_type(), &bytes)
+    };
+
+    (value, total_len)
+}
+
+/// Reads `len` bytes starting at `offset` from `payload`, zero-padding past its
+/// end. Mirrors MAVLink v2's trailing-zero-byte payload truncation, so a
+/// definition with more fields than a v2 sender actually transmitted still
+/// decodes instead of erroring.
+fn read_padded(payload: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    if offset < payload.len() {
+        let available = &payload[offset..];
+        let take = available.len().min(len);
+        buf[..take].copy_from_slice(&available[..take]);
+    }
+    buf
+}
+
+fn decode_scalar(base_type: &str, bytes: &[u8]) -> Value {
+    match base_type {
+        "uint8_t" | "uint8_t_mavlink_version" | "char" => Value::UInt8(bytes[0]),
+        "int8_t" => Value::Int8(bytes[0] as i8),
+        "uint16_t" => Value::UInt16(u16::from_le_bytes(bytes.try_into().unwrap())),
+        "int16_t" => Value::Int16(i16::from_le_bytes(bytes.try_into().unwrap())),
+        "uint32_t" => Value::UInt32(u32::from_le_bytes(bytes.try_into().unwrap())),
+        "int32_t" => Value::Int32(i32::from_le_bytes(bytes.try_into().unwrap())),
+        "float" => Value::Float(f32::from_le_bytes(bytes.try_into().unwrap())),
+        "uint64_t" => Value::UInt64(u64::from_le_bytes(bytes.try_into().unwrap())),
+        "int64_t" => Value::Int64(i64::from_le_bytes(bytes.try_into().unwrap())),
+        "double" => Value::Double(f64::from_le_bytes(bytes.try_into().unwrap())),
+        // Unrecognized type: nothing sensible to decode it as.
+        _ => Value::Array(Vec::new()),
+    }
+}
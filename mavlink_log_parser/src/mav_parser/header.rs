@@ -3,11 +3,14 @@ use std::convert::TryInto;
 
 use uuid::Uuid;
 
+use super::dialect::Dialect;
+
 /// Struct representing format flags for the log file.
 ///
 /// `FormatFlags` contains options that modify the format of the log file.
 /// - `mavlink_only`: If set, only MAVLink messages are logged allowing for a more compact log file.
 /// - `not_timestamped`: If set, timestamps per entry are not included in the log file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FormatFlags {
     /// If set, only MAVLink messages are logged allowing for a more compact log file.
     pub mavlink_only: bool,
@@ -22,6 +25,17 @@ impl FormatFlags {
             not_timestamped: packed_data & 0x02 != 0,
         }
     }
+
+    pub fn pack(&self) -> u16 {
+        let mut packed: u16 = 0;
+        if self.mavlink_only {
+            packed |= 0x01;
+        }
+        if self.not_timestamped {
+            packed |= 0x02;
+        }
+        packed
+    }
 }
 
 /// Enum representing the payload type for MAVLink message definitions.
@@ -69,6 +83,8 @@ pub struct MavlinkMessageDefinition {
     pub size: u32,
     /// Variable size message definition payload.
     pub payload: Option<Vec<u8>>,
+    /// The dialect model resolved from `payload`, if `payload_type` carried one.
+    pub resolved_dialect: Option<Dialect>,
 }
 
 impl MavlinkMessageDefinition {
@@ -87,6 +103,7 @@ impl MavlinkMessageDefinition {
                 .unwrap(),
             size: u32::from_le_bytes(packed_data[42..46].try_into().unwrap()),
             payload: None,
+            resolved_dialect: None,
         }
     }
 
@@ -101,6 +118,20 @@ impl MavlinkMessageDefinition {
             _ => {}
         }
     }
+
+    /// Packs the fixed 46-byte portion of the message definition. The variable-size
+    /// `payload`, if any, is written separately, immediately following it.
+    pub fn pack(&self) -> [u8; 46] {
+        let mut packed_data = [0u8; 46];
+        packed_data[0..4].copy_from_slice(&self.version_major.to_le_bytes());
+        packed_data[4..8].copy_from_slice(&self.version_minor.to_le_bytes());
+        let dialect_bytes = self.dialect.as_bytes();
+        let dialect_len = dialect_bytes.len().min(32);
+        packed_data[8..8 + dialect_len].copy_from_slice(&dialect_bytes[..dialect_len]);
+        packed_data[40..42].copy_from_slice(&(self.payload_type as u16).to_le_bytes());
+        packed_data[42..46].copy_from_slice(&self.size.to_le_bytes());
+        packed_data
+    }
 }
 
 /// Struct representing the file header for the log file.
@@ -146,6 +177,21 @@ impl FileHeader {
             ),
         }
     }
+
+    /// Packs the fixed 108-byte header. The message definition's variable-size
+    /// `payload`, if any, is written separately, immediately following it.
+    pub fn pack(&self) -> [u8; 108] {
+        let mut packed_data = [0u8; 108];
+        packed_data[0..16].copy_from_slice(self.uuid.as_bytes());
+        packed_data[16..24].copy_from_slice(&self.timestamp_us.to_le_bytes());
+        let app_id_bytes = self.src_application_id.as_bytes();
+        let app_id_len = app_id_bytes.len().min(32);
+        packed_data[24..24 + app_id_len].copy_from_slice(&app_id_bytes[..app_id_len]);
+        packed_data[56..60].copy_from_slice(&self.format_version.to_le_bytes());
+        packed_data[60..62].copy_from_slice(&self.format_flags.pack().to_le_bytes());
+        packed_data[62..108].copy_from_slice(&self.message_definition.pack());
+        packed_data
+    }
 }
 
 #[cfg(test)]
@@ -273,4 +319,54 @@ mod tests {
         assert_eq!(header.message_definition.size, 10);
         assert!(header.message_definition.payload.is_none());
     }
+
+    #[test]
+    fn test_file_header_pack_unpack_round_trip() {
+        let header = FileHeader {
+            uuid: Uuid::from_bytes([
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+            ]),
+            timestamp_us: 0x1122334455667788,
+            src_application_id: "app".to_string(),
+            format_version: 1,
+            format_flags: FormatFlags {
+                mavlink_only: true,
+                not_timestamped: false,
+            },
+            message_definition: MavlinkMessageDefinition {
+                version_major: 2,
+                version_minor: 0,
+                dialect: "common".to_string(),
+                payload_type: MavlinkDefinitionPayloadType::None,
+                size: 0,
+                payload: None,
+                resolved_dialect: None,
+            },
+        };
+
+        let packed = header.pack();
+        let unpacked = FileHeader::unpack(&packed);
+
+        assert_eq!(unpacked.uuid, header.uuid);
+        assert_eq!(unpacked.timestamp_us, header.timestamp_us);
+        assert_eq!(unpacked.src_application_id, header.src_application_id);
+        assert_eq!(unpacked.format_version, header.format_version);
+        assert_eq!(unpacked.format_flags, header.format_flags);
+        assert_eq!(
+            unpacked.message_definition.version_major,
+            header.message_definition.version_major
+        );
+        assert_eq!(
+            unpacked.message_definition.version_minor,
+            header.message_definition.version_minor
+        );
+        assert_eq!(
+            unpacked.message_definition.dialect,
+            header.message_definition.dialect
+        );
+        assert_eq!(
+            unpacked.message_definition.payload_type,
+            header.message_definition.payload_type
+        );
+    }
 }
@@ -0,0 +1,157 @@
+//! Sidecar-persisted `(timestamp, offset)` index backing `MavLogParser`'s
+//! `seek_to_timestamp`/`seek_to_entry`.
+//!
+//! Unlike `crate::log_index::LogIndex`, this doesn't need a `Seek`-capable reader
+//! of its own: `MavLogParser::scan_index` builds it by running a throwaway parser
+//! over the file, reusing the exact same header/format-flag dispatch and
+//! `ParseMode` handling `MavLogParser::next` already has, rather than duplicating
+//! per-format framing logic here.
+
+use std::path::PathBuf;
+
+use mavlink::{MAVLinkV1MessageRaw, MAVLinkV2MessageRaw, MavlinkVersion, Message};
+use uuid::Uuid;
+
+use super::header::FormatFlags;
+use crate::LogEntry;
+
+/// Every entry's byte offset, in file order, plus a sorted `(timestamp, index)`
+/// table for entries that carry one.
+#[derive(Default)]
+pub(crate) struct TimestampIndex {
+    /// Every entry's byte offset, in file order. Looking an entry up by its
+    /// position in this `Vec` is the "entry-count" fallback `seek_to_entry` uses
+    /// for logs with no timestamps to sort by.
+    pub(crate) offsets: Vec<u64>,
+    /// `(timestamp, index into offsets)` for every entry with a timestamp, sorted
+    /// by timestamp, for `seek_to_timestamp`'s binary search.
+    pub(crate) by_time: Vec<(u64, usize)>,
+}
+
+impl TimestampIndex {
+    /// Loads a previously saved sidecar index, if one exists next to `file_path`
+    /// and was built for a file with this exact header `uuid`. Returns `None` on
+    /// any mismatch, missing file, or corruption -- the caller falls back to
+    /// rebuilding the index from scratch rather than treating this as fatal.
+    pub(crate) fn load_sidecar(file_path: &str, uuid: Uuid) -> Option<Self> {
+        let data = std::fs::read(Self::sidecar_path(file_path)).ok()?;
+        Self::unpack(&data, uuid)
+    }
+
+    /// Writes this index to its sidecar path so a later open of the same file can
+    /// skip rebuilding it. Failure isn't fatal -- the index already built is still
+    /// usable for this session, it just won't be persisted for next time.
+    pub(crate) fn save_sidecar(&self, file_path: &str, uuid: Uuid) {
+        let _ = std::fs::write(Self::sidecar_path(file_path), self.pack(uuid));
+    }
+
+    fn sidecar_path(file_path: &str) -> PathBuf {
+        let mut path = PathBuf::from(file_path);
+        let mut file_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+        file_name.push(".idx");
+        path.set_file_name(file_name);
+        path
+    }
+
+    /// Packs this index as `uuid(16) | offsets.len()(8) | offsets(8 each) |
+    /// by_time.len()(8) | by_time(16 each: timestamp(8) + index(8))`, all
+    /// little-endian, mirroring `FileHeader::pack`'s manual binary layout.
+    fn pack(&self, uuid: Uuid) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(16 + 8 + self.offsets.len() * 8 + 8 + self.by_time.len() * 16);
+        buf.extend_from_slice(uuid.as_bytes());
+        buf.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        for offset in &self.offsets {
+            buf.extend_from_slice(&offset.to_le_bytes());
+        }
+        buf.extend_from_slice(&(self.by_time.len() as u64).to_le_bytes());
+        for (timestamp, index) in &self.by_time {
+            buf.extend_from_slice(&timestamp.to_le_bytes());
+            buf.extend_from_slice(&(*index as u64).to_le_bytes());
+        }
+        buf
+    }
+
+    fn unpack(data: &[u8], expected_uuid: Uuid) -> Option<Self> {
+        if data.len() < 16 + 8 {
+            return None;
+        }
+        if Uuid::from_bytes(data[0..16].try_into().ok()?) != expected_uuid {
+            return None;
+        }
+        let mut pos = 16;
+        let offsets_len = u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?) as usize;
+        pos += 8;
+        let mut offsets = Vec::with_capacity(offsets_len.min(data.len() / 8));
+        for _ in 0..offsets_len {
+            offsets.push(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?));
+            pos += 8;
+        }
+        let by_time_len = u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?) as usize;
+        pos += 8;
+        let mut by_time = Vec::with_capacity(by_time_len.min(data.len() / 16));
+        for _ in 0..by_time_len {
+            let timestamp = u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?);
+            pos += 8;
+            let index = u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?) as usize;
+            pos += 8;
+            by_time.push((timestamp, index));
+        }
+        Some(Self { offsets, by_time })
+    }
+}
+
+/// The exact number of bytes `entry` occupied on disk in the native mav-log wire
+/// format described by `format_flags`/`mav_version`, re-derived from the entry's
+/// own decoded fields rather than from anything the reader buffered while
+/// producing it -- mirrors `tlog_parser::read_tlog_record`'s reserialize-to-
+/// measure-length technique.
+///
+/// Mixed-format framing is `type_tag(1) + [timestamp(8)] + size(2) + payload`;
+/// mavlink-only framing is `[timestamp(8)] + self-delimited MAVLink frame`, per
+/// `MavLogWriter::frame_and_write`.
+pub(crate) fn entry_wire_size<M: Message>(
+    entry: &LogEntry<M>,
+    format_flags: FormatFlags,
+    mav_version: MavlinkVersion,
+) -> u64 {
+    let timestamp_len: u64 = if format_flags.not_timestamped { 0 } else { 8 };
+
+    if format_flags.mavlink_only {
+        match (entry.mav_header, &entry.mav_message) {
+            (Some(header), Some(message)) => {
+                timestamp_len + mavlink_frame_len(mav_version, header, message) as u64
+            }
+            _ => panic!("mav_parser always decodes a header and message for a mavlink-only entry"),
+        }
+    } else {
+        let payload_len: u64 = if let (Some(header), Some(message)) = (entry.mav_header, &entry.mav_message) {
+            mavlink_frame_len(mav_version, header, message) as u64
+        } else if let Some(text) = &entry.text {
+            text.len() as u64
+        } else if let Some(raw) = &entry.raw {
+            raw.len() as u64
+        } else {
+            0
+        };
+        1 + timestamp_len + 2 + payload_len
+    }
+}
+
+fn mavlink_frame_len<M: Message>(
+    mav_version: MavlinkVersion,
+    header: mavlink::MavHeader,
+    message: &M,
+) -> usize {
+    match mav_version {
+        MavlinkVersion::V1 => {
+            let mut frame = MAVLinkV1MessageRaw::new();
+            frame.serialize_message(header, message);
+            frame.raw_bytes().len()
+        }
+        MavlinkVersion::V2 => {
+            let mut frame = MAVLinkV2MessageRaw::new();
+            frame.serialize_message(header, message);
+            frame.raw_bytes().len()
+        }
+    }
+}
@@ -1,15 +1,28 @@
-mod header;
+pub mod dialect;
+pub mod dynamic;
+pub mod header;
+mod index;
+#[cfg(feature = "async")]
+pub mod async_parser;
+pub mod recorder;
+pub mod writer;
 
+use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::rc::Rc;
 
 use mavlink::error::MessageReadError;
 use mavlink::peek_reader::PeekReader;
 use mavlink::{read_versioned_msg, MavlinkVersion, Message};
+use uuid::Uuid;
 
+use crate::filter::PeekedFrame;
 use crate::{LogEntry, MavParser};
-use header::{FileHeader, MavlinkDefinitionPayloadType};
+use header::{FileHeader, FormatFlags, MavlinkDefinitionPayloadType};
+use index::TimestampIndex;
 
 /// Enum representing the type of log entry.
 ///
@@ -17,6 +30,7 @@ use header::{FileHeader, MavlinkDefinitionPayloadType};
 /// - `Raw`: Raw binary data.
 /// - `Mavlink`: MAVLink message.
 /// - `Utf8Text`: UTF-8 encoded text.
+#[derive(Clone, Copy, PartialEq, Debug)]
 enum EntryType {
     Raw = 0,
     Mavlink = 1,
@@ -36,17 +50,146 @@ impl TryFrom<u8> for EntryType {
     }
 }
 
+/// Controls how tolerant `MavLogParser` is of corrupted framing in the underlying stream.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ParseMode {
+    /// Preserve the parser's original behavior: if a frame is not found exactly where
+    /// expected, fall through to `read_versioned_msg`'s built-in magic-number search,
+    /// which silently resyncs on corrupt data. A resynced message can end up associated
+    /// with the wrong timestamp, or a non-MAVLink entry can be skipped over entirely.
+    #[default]
+    Lenient,
+    /// Require a frame to start exactly where it is expected instead of letting
+    /// `read_versioned_msg` scan forward for one. Any deviation is reported as an error
+    /// rather than silently resynced.
+    Strict,
+    /// Like `Strict`, every record is validated rather than silently resynced, but a
+    /// failed record doesn't abort iteration: `next()` scans forward byte by byte
+    /// looking for the next plausible entry boundary, recording what it skipped in
+    /// `MavLogParser::recovery_stats`. Meant for real flight logs that get truncated
+    /// or corrupted by a power loss mid-write.
+    ///
+    /// Applies to `TimestampedMavlinkOnlyParser` and `MixedParser`. A mavlink-only log
+    /// without timestamps already benefits from `read_versioned_msg`'s built-in
+    /// magic-number resync and has no per-record framing to validate, so `Recover`
+    /// behaves the same as `Lenient` there.
+    Recover,
+}
+
+/// Counts of bytes skipped and boundaries recovered by `ParseMode::Recover`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RecoveryStats {
+    /// Total bytes skipped while scanning for the next plausible entry boundary.
+    pub skipped_bytes: u64,
+    /// Number of times a skip scan successfully found a valid boundary.
+    pub resyncs: u64,
+}
+
+/// Builds the error `Strict` mode returns when a frame does not start where expected.
+///
+/// `MessageReadError` is defined upstream in the `mavlink` crate and cannot be extended
+/// with a new variant from here, so the offset/expected/found details are encoded into
+/// the message of an `Io` error instead.
+fn desync_error(offset: u64, expected: u8, found: u8) -> MessageReadError {
+    MessageReadError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+            "desync at offset {offset}: expected magic number 0x{expected:02x}, found 0x{found:02x}"
+        ),
+    ))
+}
+
+/// Reads a `len`-byte payload into a freshly allocated `Vec`, treating `len` (which
+/// usually comes straight off the wire, e.g. a `payload_size` field) as untrusted.
+///
+/// Allocation failure is reported as a `MessageReadError` instead of aborting the
+/// process, mirroring the fallible-allocation style mp4parse uses for untrusted
+/// structural fields. There's no portable way to check `len` against "remaining
+/// readable bytes" for an arbitrary `Read` source, so the read itself is what
+/// actually bounds a corrupt or truncated length: it errors instead of blocking
+/// forever or succeeding with garbage past the real end of the stream.
+fn read_untrusted_payload<R: Read>(
+    reader: &mut PeekReader<R>,
+    len: usize,
+) -> Result<Vec<u8>, MessageReadError> {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.try_reserve_exact(len).map_err(|err| {
+        MessageReadError::Io(std::io::Error::new(
+            std::io::ErrorKind::OutOfMemory,
+            format!("failed to allocate {len} bytes for a log entry payload: {err}"),
+        ))
+    })?;
+    buf.extend_from_slice(reader.read_exact(len)?);
+    Ok(buf)
+}
+
+/// The number of a self-delimited MAVLink v1/v2 frame's leading bytes that
+/// `parse_mav_frame_header` needs peeked before it can read the frame's total
+/// length and message id.
+fn mav_frame_header_len(mav_version: MavlinkVersion) -> usize {
+    match mav_version {
+        MavlinkVersion::V1 => 6,
+        MavlinkVersion::V2 => 10,
+    }
+}
+
+/// The header fields of a self-delimited MAVLink v1/v2 frame readable
+/// straight off its wire bytes, without decoding the rest of the frame.
+struct PeekedFrameHeader {
+    /// Total on-wire length of the frame (header + payload + CRC + optional
+    /// v2 signature).
+    frame_len: usize,
+    message_id: u32,
+    system_id: u8,
+    component_id: u8,
+}
+
+/// Reads a self-delimited MAVLink v1/v2 frame's header fields straight off its
+/// wire bytes, without decoding the rest of the frame. `header` must hold at
+/// least `mav_frame_header_len(mav_version)` bytes already peeked from
+/// wherever the frame starts; this never touches a reader itself. Mirrors
+/// `dynamic::split_frame`'s header layout.
+fn parse_mav_frame_header(header: &[u8], mav_version: MavlinkVersion) -> PeekedFrameHeader {
+    match mav_version {
+        MavlinkVersion::V1 => {
+            // STX(1) LEN(1) SEQ(1) SYSID(1) COMPID(1) MSGID(1) PAYLOAD(LEN) CRC(2)
+            let payload_len = header[1] as usize;
+            PeekedFrameHeader {
+                frame_len: 6 + payload_len + 2,
+                message_id: header[5] as u32,
+                system_id: header[3],
+                component_id: header[4],
+            }
+        }
+        MavlinkVersion::V2 => {
+            // STX(1) LEN(1) INCOMPAT(1) COMPAT(1) SEQ(1) SYSID(1) COMPID(1)
+            // MSGID(3) PAYLOAD(LEN) CRC(2) [SIGNATURE(13) if INCOMPAT & 0x01]
+            let payload_len = header[1] as usize;
+            let signed = header[2] & 0x01 != 0;
+            PeekedFrameHeader {
+                frame_len: 10 + payload_len + 2 + if signed { 13 } else { 0 },
+                message_id: u32::from_le_bytes([header[7], header[8], header[9], 0]),
+                system_id: header[5],
+                component_id: header[6],
+            }
+        }
+    }
+}
+
 /// Parser for MAVLink-only log files without timestamps.
 ///
 /// This parser assumes the log file contains only MAVLink messages and no timestamps.
 /// It reads MAVLink messages sequentially from the file.
-struct MavlinkOnlyNoTimestampParser<M: Message> {
-    reader: PeekReader<File>,
+///
+/// Generic over the underlying reader `R`, so the same parser can run against a file,
+/// a decompressed in-memory stream, or a network socket.
+struct MavlinkOnlyNoTimestampParser<R: Read, M: Message> {
+    reader: PeekReader<R>,
     mav_version: MavlinkVersion,
     _phantom: std::marker::PhantomData<M>,
 }
 
-impl<M: Message> MavParser for MavlinkOnlyNoTimestampParser<M> {
+impl<R: Read, M: Message> MavParser for MavlinkOnlyNoTimestampParser<R, M> {
     type M = M;
 
     /// Reads the next MAVLink message from the log file.
@@ -74,29 +217,163 @@ impl<M: Message> MavParser for MavlinkOnlyNoTimestampParser<M> {
         // it tries to unpack the current data and gets something unexpected. Since this is a mavlink only file with
         // no timestamps, we can safely allow this to happen. The Mavlink infrastructure has a lot of hours and false
         // positives in the magic number search do not seem like a problem with Mavlink only data streams.
-        let (header, message) = read_versioned_msg::<M, File>(&mut self.reader, self.mav_version)?;
+        let (header, message) = read_versioned_msg::<M, R>(&mut self.reader, self.mav_version)?;
         entry.mav_header = Some(header);
         entry.mav_message = Some(message);
         Ok(entry)
     }
+
+    /// Peeks the next frame's header fields straight off the wire, as long as
+    /// it actually starts with this version's magic number right here; if it
+    /// doesn't, only `read_versioned_msg`'s resync scan in `next` can find the
+    /// next real frame, so there's nothing cheaper to peek.
+    fn peek_frame(&mut self) -> Result<Option<PeekedFrame>, MessageReadError> {
+        let magic_number: u8 = match self.mav_version {
+            MavlinkVersion::V1 => mavlink::MAV_STX,
+            MavlinkVersion::V2 => mavlink::MAV_STX_V2,
+        };
+        let peeked = self.reader.peek_exact(mav_frame_header_len(self.mav_version))?;
+        if peeked[0] != magic_number {
+            return Ok(None);
+        }
+        let header = parse_mav_frame_header(peeked, self.mav_version);
+        Ok(Some(PeekedFrame {
+            message_id: header.message_id,
+            system_id: header.system_id,
+            component_id: header.component_id,
+        }))
+    }
+
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        let magic_number: u8 = match self.mav_version {
+            MavlinkVersion::V1 => mavlink::MAV_STX,
+            MavlinkVersion::V2 => mavlink::MAV_STX_V2,
+        };
+        let peeked = self.reader.peek_exact(mav_frame_header_len(self.mav_version))?;
+        if peeked[0] != magic_number {
+            return Ok(false);
+        }
+        let frame_len = parse_mav_frame_header(peeked, self.mav_version).frame_len;
+        read_untrusted_payload(&mut self.reader, frame_len)?;
+        Ok(true)
+    }
 }
 
 /// Parser for MAVLink-only log files with timestamps.
 ///
 /// This parser assumes the log file contains only MAVLink type data, each preceded by a timestamp.
 /// It reads MAVLink messages and their associated timestamps sequentially from the file.
-struct TimestampedMavlinkOnlyParser<M: Message> {
-    reader: PeekReader<File>,
+///
+/// Generic over the underlying reader `R`, so the same parser can run against a file,
+/// a decompressed in-memory stream, or a network socket.
+struct TimestampedMavlinkOnlyParser<R: Read, M: Message> {
+    reader: PeekReader<R>,
     mav_version: MavlinkVersion,
+    parse_mode: ParseMode,
+    offset: u64,
+    /// The last entry's timestamp successfully returned, used by `ParseMode::Recover`
+    /// to reject a resync landing on a frame with an implausibly out-of-order timestamp.
+    last_timestamp: Option<u64>,
+    /// Shared with the owning `MavLogParser`, which exposes it via `recovery_stats`.
+    recovery_stats: Rc<RefCell<RecoveryStats>>,
     _phantom: std::marker::PhantomData<M>,
 }
 
-impl<M: Message> MavParser for TimestampedMavlinkOnlyParser<M> {
+impl<R: Read, M: Message> TimestampedMavlinkOnlyParser<R, M> {
+    /// The non-recovering parse attempt `next()` wraps in a retry loop under
+    /// `ParseMode::Recover`.
+    fn try_next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        let mut entry: LogEntry<M> = LogEntry::default();
+        let magic_number: u8 = match self.mav_version {
+            MavlinkVersion::V1 => mavlink::MAV_STX,
+            MavlinkVersion::V2 => mavlink::MAV_STX_V2,
+        };
+        let peeked = self.reader.peek_exact(9)?;
+        if peeked[8] == magic_number {
+            let timestamp_raw: &[u8] = self.reader.read_exact(8)?;
+            entry.timestamp = match timestamp_raw.try_into() {
+                Ok(bytes) => Some(u64::from_le_bytes(bytes)),
+                Err(_) => None,
+            };
+            self.offset += 8;
+        } else if self.parse_mode != ParseMode::Lenient {
+            // No timestamp means the frame isn't starting where it should either; in
+            // Lenient mode we'd fall through to read_versioned_msg's resync below, but
+            // Strict and Recover both refuse to let it scan forward looking for the
+            // next one without accounting for what was skipped.
+            return Err(desync_error(self.offset + 8, magic_number, peeked[8]));
+        }
+        if self.parse_mode == ParseMode::Lenient {
+            // WARNING: this will silently fail and try to get the next mavlink message on
+            // data corruption; this is a concern that some messages could be associated
+            // with the wrong timestamp.
+            let (header, message) = read_versioned_msg::<M, R>(&mut self.reader, self.mav_version)?;
+            entry.mav_header = Some(header);
+            entry.mav_message = Some(message);
+            return Ok(entry);
+        }
+        // Strict and Recover: bound the read to exactly this frame's declared length,
+        // mirroring MixedParser::try_next's EntryType::Mavlink handling, so a corrupt
+        // or truncated body (including a CRC mismatch) runs out of bytes and errors
+        // instead of read_versioned_msg resyncing past it into whatever follows in the
+        // stream.
+        let start_offset = self.offset;
+        let frame_len = self.peek_frame_len()?;
+        let frame = read_untrusted_payload(&mut self.reader, frame_len)?;
+        self.offset += frame_len as u64;
+        let mut frame_reader = PeekReader::new(std::io::Cursor::new(frame));
+        let (header, message) =
+            read_versioned_msg::<M, std::io::Cursor<Vec<u8>>>(&mut frame_reader, self.mav_version)
+                .map_err(|err| {
+                    MessageReadError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "mavlink frame at offset {start_offset} of declared length \
+                             {frame_len} failed to validate: {err:?}"
+                        ),
+                    ))
+                })?;
+        if frame_reader.read_u8().is_ok() {
+            return Err(MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "mavlink frame at offset {start_offset} did not consume its entire \
+                     declared length of {frame_len}"
+                ),
+            )));
+        }
+        entry.mav_header = Some(header);
+        entry.mav_message = Some(message);
+        Ok(entry)
+    }
+
+    /// Peeks this self-delimited MAVLink frame far enough to learn its total
+    /// on-wire length (header + payload + CRC + optional v2 signature) without
+    /// consuming anything.
+    fn peek_frame_len(&mut self) -> Result<usize, MessageReadError> {
+        let header = self.reader.peek_exact(mav_frame_header_len(self.mav_version))?;
+        Ok(parse_mav_frame_header(header, self.mav_version).frame_len)
+    }
+
+    /// Skips a single byte, accounting for it in `skipped_this_call` so the caller can
+    /// fold it into `recovery_stats` once a boundary is found.
+    fn skip_one_byte(&mut self, skipped_this_call: &mut u64) -> Result<(), MessageReadError> {
+        self.reader.read_u8()?;
+        self.offset += 1;
+        *skipped_this_call += 1;
+        Ok(())
+    }
+}
+
+impl<R: Read, M: Message> MavParser for TimestampedMavlinkOnlyParser<R, M> {
     type M = M;
 
     /// Reads the next MAVLink message and its timestamp from the log file.
     ///
-    /// If the data is corrupted, it will silently fail and attempt to read the next MAVLink message.
+    /// In `ParseMode::Lenient`, corrupted data is silently skipped by `read_versioned_msg`'s
+    /// own magic-number search. In `ParseMode::Recover`, a failed record instead triggers a
+    /// byte-by-byte scan for the next plausible boundary (a MAVLink start byte with a
+    /// timestamp no earlier than the last one returned), recorded in `recovery_stats`.
     ///
     /// # Returns
     ///
@@ -108,31 +385,89 @@ impl<M: Message> MavParser for TimestampedMavlinkOnlyParser<M> {
     /// This includes:
     /// - I/O errors while reading from the file.
     /// - Corrupted MAVLink packets or invalid timestamps.
+    /// - In `ParseMode::Strict`, a frame that does not start exactly where expected, or
+    ///   one that does but fails to validate (e.g. a CRC mismatch) within its own
+    ///   declared length.
     ///
     /// # Panics
     ///
     /// Panics if the `peek_exact` or `read_exact` methods encounter an unrecoverable error.
     ///
     fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
-        let mut entry: LogEntry<M> = LogEntry::default();
+        if self.parse_mode != ParseMode::Recover {
+            return self.try_next();
+        }
+        let mut skipped_this_call: u64 = 0;
+        loop {
+            match self.try_next() {
+                Ok(entry) => {
+                    if let (Some(timestamp), Some(last)) = (entry.timestamp, self.last_timestamp) {
+                        if timestamp < last {
+                            self.skip_one_byte(&mut skipped_this_call)?;
+                            continue;
+                        }
+                    }
+                    if let Some(timestamp) = entry.timestamp {
+                        self.last_timestamp = Some(timestamp);
+                    }
+                    if skipped_this_call > 0 {
+                        let mut stats = self.recovery_stats.borrow_mut();
+                        stats.skipped_bytes += skipped_this_call;
+                        stats.resyncs += 1;
+                    }
+                    return Ok(entry);
+                }
+                Err(_) => self.skip_one_byte(&mut skipped_this_call)?,
+            }
+        }
+    }
+
+    /// Only supported in `ParseMode::Lenient`: `Strict`/`Recover` rely on
+    /// `try_next` actually validating a frame's CRC within its declared length
+    /// (see its doc comment) before trusting that length to skip by, and a
+    /// peek can't run that validation without decoding. In `Lenient`, where
+    /// `next` already trusts an in-place frame's declared length without a CRC
+    /// check, peeking it first costs nothing extra.
+    fn peek_frame(&mut self) -> Result<Option<PeekedFrame>, MessageReadError> {
+        if self.parse_mode != ParseMode::Lenient {
+            return Ok(None);
+        }
         let magic_number: u8 = match self.mav_version {
             MavlinkVersion::V1 => mavlink::MAV_STX,
             MavlinkVersion::V2 => mavlink::MAV_STX_V2,
         };
-        if self.reader.peek_exact(9)?[8] == magic_number {
-            let timestamp_raw: &[u8] = self.reader.read_exact(8)?;
-            entry.timestamp = match timestamp_raw.try_into() {
-                Ok(bytes) => Some(u64::from_le_bytes(bytes)),
-                Err(_) => None,
-            };
+        let peeked = self.reader.peek_exact(8 + mav_frame_header_len(self.mav_version))?;
+        if peeked[8] != magic_number {
+            // Framing looks off; only read_versioned_msg's resync scan in
+            // try_next can find the next real frame start.
+            return Ok(None);
         }
-        // WARNING: this will silently fail and try to get next mavlink message on data corruption
-        // this is a concern that some messages could be associated with the wrong timestamp
-        // we need a version of this to fail immediately on any parsing issue
-        let (header, message) = read_versioned_msg::<M, File>(&mut self.reader, self.mav_version)?;
-        entry.mav_header = Some(header);
-        entry.mav_message = Some(message);
-        Ok(entry)
+        let header = parse_mav_frame_header(&peeked[8..], self.mav_version);
+        Ok(Some(PeekedFrame {
+            message_id: header.message_id,
+            system_id: header.system_id,
+            component_id: header.component_id,
+        }))
+    }
+
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        if self.parse_mode != ParseMode::Lenient {
+            return Ok(false);
+        }
+        let magic_number: u8 = match self.mav_version {
+            MavlinkVersion::V1 => mavlink::MAV_STX,
+            MavlinkVersion::V2 => mavlink::MAV_STX_V2,
+        };
+        let peeked = self.reader.peek_exact(9)?;
+        if peeked[8] != magic_number {
+            return Ok(false);
+        }
+        self.reader.read_exact(8)?;
+        self.offset += 8;
+        let frame_len = self.peek_frame_len()?;
+        read_untrusted_payload(&mut self.reader, frame_len)?;
+        self.offset += frame_len as u64;
+        Ok(true)
     }
 }
 
@@ -140,77 +475,106 @@ impl<M: Message> MavParser for TimestampedMavlinkOnlyParser<M> {
 ///
 /// This parser can handle log files with raw data, MAVLink messages, and UTF-8 text entries.
 /// It also supports optional timestamps for each entry.
-pub struct MixedParser<M: Message> {
+///
+/// Generic over the underlying reader `R`, so the same parser can run against a file,
+/// a decompressed in-memory stream, or a network socket.
+pub struct MixedParser<R: Read, M: Message> {
     timestamped: bool,
-    reader: PeekReader<File>,
+    reader: PeekReader<R>,
     mav_version: MavlinkVersion,
+    parse_mode: ParseMode,
+    offset: u64,
+    /// The last entry's timestamp successfully returned, used by `ParseMode::Recover`
+    /// to reject a resync landing on an entry with an implausibly out-of-order timestamp.
+    last_timestamp: Option<u64>,
+    /// Shared with the owning `MavLogParser`, which exposes it via `recovery_stats`.
+    recovery_stats: Rc<RefCell<RecoveryStats>>,
     _phantom: std::marker::PhantomData<M>,
 }
 
-impl<M: Message> MavParser for MixedParser<M> {
-    type M = M;
-
-    /// Reads the next log entry from the file.
-    ///
-    /// Determines the entry type and processes it accordingly:
-    /// - `Raw`: Reads raw binary data.
-    /// - `Mavlink`: Reads a MAVLink message.
-    /// - `Utf8Text`: Reads UTF-8 encoded text.
-    /// If timestamps are enabled, reads the timestamp for the entry.
-    ///
-    /// # Returns
-    ///
-    /// A `LogEntry` containing the parsed data, which may include a timestamp, MAVLink message, or text.
-    ///
-    /// # Errors
-    ///
-    /// Returns a `MessageReadError` if there is an issue parsing the log entry data. This includes:
-    /// - I/O errors while reading from the file.
-    /// - Corrupted MAVLink packets or invalid UTF-8 text.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the entry payload size cannot be read because this is unrecoverable.
-    ///
-    fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+impl<R: Read, M: Message> MixedParser<R, M> {
+    /// The non-recovering parse attempt `next()` wraps in a retry loop under
+    /// `ParseMode::Recover`.
+    fn try_next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
         let mut entry: LogEntry<M> = LogEntry::default();
         let entry_type: EntryType = self
             .reader
             .read_u8()
             // If entry type is unknown default to raw
             .map(|value| value.try_into().unwrap_or(EntryType::Raw))?;
+        self.offset += 1;
         if self.timestamped {
             let timestamp_raw: &[u8] = self.reader.read_exact(8)?;
             entry.timestamp = match timestamp_raw.try_into() {
                 Ok(bytes) => Some(u64::from_le_bytes(bytes)),
                 Err(_) => None,
             };
+            self.offset += 8;
         }
-        let payload_size: u16 = u16::from_le_bytes(
-            self.reader
-                .read_exact(2)?
-                .try_into()
-                .expect("Failed to read log entry payload size."),
-        );
+        let payload_size_raw = self.reader.read_exact(2)?;
+        let payload_size: u16 = u16::from_le_bytes(payload_size_raw.try_into().map_err(|_| {
+            MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "failed to read log entry payload size",
+            ))
+        })?);
+        self.offset += 2;
         match entry_type {
             EntryType::Raw => {
-                let payload = self.reader.read_exact(payload_size as usize)?;
-                entry.raw = Some(payload.to_vec())
+                let payload = read_untrusted_payload(&mut self.reader, payload_size as usize)?;
+                entry.raw = Some(payload);
+                self.offset += payload_size as u64;
+            }
+            EntryType::Mavlink if self.parse_mode != ParseMode::Lenient => {
+                // Bound the read to exactly the declared payload size so a corrupt or
+                // truncated frame runs out of bytes instead of read_versioned_msg
+                // resyncing into whatever entry happens to follow it in the stream.
+                let start_offset = self.offset;
+                let payload = read_untrusted_payload(&mut self.reader, payload_size as usize)?;
+                self.offset += payload_size as u64;
+                let mut frame_reader = PeekReader::new(std::io::Cursor::new(payload));
+                let (header, message) = read_versioned_msg::<M, std::io::Cursor<Vec<u8>>>(
+                    &mut frame_reader,
+                    self.mav_version,
+                )
+                .map_err(|err| {
+                    MessageReadError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "mavlink entry at offset {start_offset} did not fit its declared \
+                             payload_size of {payload_size}: {err:?}"
+                        ),
+                    ))
+                })?;
+                // The frame should have consumed exactly `payload_size` bytes; if any are
+                // left over, the frame was shorter than declared rather than corrupt, and
+                // we'd otherwise silently drop the remainder on the floor.
+                if frame_reader.read_u8().is_ok() {
+                    return Err(MessageReadError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "mavlink entry at offset {start_offset} did not consume its entire \
+                             declared payload_size of {payload_size}"
+                        ),
+                    )));
+                }
+                entry.mav_header = Some(header);
+                entry.mav_message = Some(message);
+                return Ok(entry);
             }
             EntryType::Mavlink => {
                 // WARNING: this will silently fail and try to get next mavlink message on data corruption
                 // this is a concern that some messages could be associated with the wrong timestamp
                 // or non mavlink entries could get skipped
-                // we need a version of this to fail immediately on any parsing issue
                 let (header, message) =
-                    read_versioned_msg::<M, File>(&mut self.reader, self.mav_version)?;
+                    read_versioned_msg::<M, R>(&mut self.reader, self.mav_version)?;
                 entry.mav_header = Some(header);
                 entry.mav_message = Some(message);
                 return Ok(entry);
             }
             EntryType::Utf8Text => {
-                let payload = self.reader.read_exact(payload_size as usize)?;
-                entry.text = match String::from_utf8(payload.to_vec()) {
+                let payload = read_untrusted_payload(&mut self.reader, payload_size as usize)?;
+                entry.text = match String::from_utf8(payload) {
                     Ok(text) => Some(text),
                     Err(_) => {
                         return Err(MessageReadError::Io(std::io::Error::new(
@@ -223,6 +587,123 @@ impl<M: Message> MavParser for MixedParser<M> {
         }
         Ok(entry)
     }
+
+    fn skip_one_byte(&mut self, skipped_this_call: &mut u64) -> Result<(), MessageReadError> {
+        self.reader.read_u8()?;
+        self.offset += 1;
+        *skipped_this_call += 1;
+        Ok(())
+    }
+}
+
+impl<R: Read, M: Message> MavParser for MixedParser<R, M> {
+    type M = M;
+
+    /// Reads the next log entry from the file.
+    ///
+    /// Determines the entry type and processes it accordingly:
+    /// - `Raw`: Reads raw binary data.
+    /// - `Mavlink`: Reads a MAVLink message.
+    /// - `Utf8Text`: Reads UTF-8 encoded text.
+    /// If timestamps are enabled, reads the timestamp for the entry.
+    ///
+    /// In `ParseMode::Recover`, a failed record doesn't abort iteration: instead
+    /// this scans forward byte by byte for the next record whose framing validates
+    /// and whose timestamp is monotonically sane relative to the last entry
+    /// returned, recording what it skipped in the shared `RecoveryStats`.
+    ///
+    /// # Returns
+    ///
+    /// A `LogEntry` containing the parsed data, which may include a timestamp, MAVLink message, or text.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if there is an issue parsing the log entry data. This includes:
+    /// - I/O errors while reading from the file.
+    /// - Corrupted MAVLink packets or invalid UTF-8 text.
+    /// - In `ParseMode::Strict`, a MAVLink entry whose frame does not consume exactly
+    ///   its declared `payload_size`.
+    /// - A truncated entry payload-size field.
+    ///
+    fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
+        if self.parse_mode != ParseMode::Recover {
+            return self.try_next();
+        }
+        let mut skipped_this_call: u64 = 0;
+        loop {
+            match self.try_next() {
+                Ok(entry) => {
+                    if let (Some(timestamp), Some(last)) = (entry.timestamp, self.last_timestamp) {
+                        if timestamp < last {
+                            self.skip_one_byte(&mut skipped_this_call)?;
+                            continue;
+                        }
+                    }
+                    if let Some(timestamp) = entry.timestamp {
+                        self.last_timestamp = Some(timestamp);
+                    }
+                    if skipped_this_call > 0 {
+                        let mut stats = self.recovery_stats.borrow_mut();
+                        stats.skipped_bytes += skipped_this_call;
+                        stats.resyncs += 1;
+                    }
+                    return Ok(entry);
+                }
+                Err(_) => self.skip_one_byte(&mut skipped_this_call)?,
+            }
+        }
+    }
+
+    /// Only supported in `ParseMode::Lenient`, for the same reason as
+    /// `TimestampedMavlinkOnlyParser::peek_frame`: `Strict`/`Recover` need
+    /// `try_next`'s actual CRC validation before trusting a payload_size to
+    /// skip by, which a peek can't run without decoding.
+    fn peek_frame(&mut self) -> Result<Option<PeekedFrame>, MessageReadError> {
+        if self.parse_mode != ParseMode::Lenient {
+            return Ok(None);
+        }
+        // Check the type byte alone first, so a short non-MAVLink entry at the
+        // end of the file isn't mistaken for a truncated frame header.
+        let entry_type: EntryType = self.reader.peek_exact(1)?[0].try_into().unwrap_or(EntryType::Raw);
+        if entry_type != EntryType::Mavlink {
+            return Ok(None);
+        }
+        let prefix_len = 1 + if self.timestamped { 8 } else { 0 } + 2;
+        let peeked = self
+            .reader
+            .peek_exact(prefix_len + mav_frame_header_len(self.mav_version))?;
+        let header = parse_mav_frame_header(&peeked[prefix_len..], self.mav_version);
+        Ok(Some(PeekedFrame {
+            message_id: header.message_id,
+            system_id: header.system_id,
+            component_id: header.component_id,
+        }))
+    }
+
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        if self.parse_mode != ParseMode::Lenient {
+            return Ok(false);
+        }
+        let entry_type: EntryType = self.reader.peek_exact(1)?[0].try_into().unwrap_or(EntryType::Raw);
+        if entry_type != EntryType::Mavlink {
+            return Ok(false);
+        }
+        let prefix_len = 1 + if self.timestamped { 8 } else { 0 } + 2;
+        let peeked = self
+            .reader
+            .peek_exact(prefix_len + mav_frame_header_len(self.mav_version))?;
+        // Re-derive payload_size the same way try_next does, then consume and
+        // discard the header and payload instead of decoding it.
+        let payload_size = u16::from_le_bytes([
+            peeked[prefix_len - 2],
+            peeked[prefix_len - 1],
+        ]) as usize;
+        self.reader.read_exact(prefix_len as usize)?;
+        self.offset += prefix_len as u64;
+        read_untrusted_payload(&mut self.reader, payload_size)?;
+        self.offset += payload_size as u64;
+        Ok(true)
+    }
 }
 
 /// High-level parser for MAVLink log files.
@@ -231,6 +712,23 @@ impl<M: Message> MavParser for MixedParser<M> {
 /// It supports MAVLink-only files (with or without timestamps) and mixed log files.
 pub struct MavLogParser<M: Message + 'static> {
     parser: Box<dyn MavParser<M = M>>,
+    dialect: Option<dialect::Dialect>,
+    recovery_stats: Rc<RefCell<RecoveryStats>>,
+    /// `Some` only when constructed via `new`, since `seek_to_offset` needs to be
+    /// able to reopen and `Seek` the underlying file; a `from_reader` source (a
+    /// socket, say) generally can't be.
+    file_path: Option<String>,
+    uuid: Uuid,
+    format_flags: FormatFlags,
+    mav_version: MavlinkVersion,
+    parse_mode: ParseMode,
+    /// Byte length of the file header this parser was opened past, i.e. the file
+    /// offset of the first entry: `108 + header.message_definition.size`. Used by
+    /// `scan_index` to seed its offset accumulator.
+    header_len: u64,
+    /// Lazily built by `build_index` (or implicitly by the first `seek_to_timestamp`/
+    /// `seek_to_entry` call), and reused afterwards.
+    index: Option<TimestampIndex>,
 }
 
 impl<M: Message + 'static> MavLogParser<M> {
@@ -241,25 +739,99 @@ impl<M: Message + 'static> MavLogParser<M> {
     /// # Arguments
     ///
     /// - `file_path`: Path to the log file.
+    /// - `parse_mode`: How tolerant the parser should be of corrupted framing.
+    ///   Defaults to `ParseMode::Lenient` when `None`, preserving the original behavior.
     ///
     /// # Returns
     ///
     /// An instance of `MavLogParser` initialized with the appropriate parser.
     ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if the file header could not be read, e.g. because
+    /// the file is truncated before the end of the 108-byte header, or if it is
+    /// structurally invalid (unsupported format or MAVLink version).
+    ///
     /// # Panics
     ///
-    /// Panics if the file header cannot be read or if the format is unsupported.
+    /// Panics if the file cannot be opened.
     ///
-    pub fn new(file_path: &str) -> Self {
+    pub fn new(file_path: &str, parse_mode: Option<ParseMode>) -> Result<Self, MessageReadError> {
         let file: File = File::open(file_path).expect("Failed to open file");
-        let mut reader: PeekReader<File> = PeekReader::new(file);
+        let mut parser = Self::from_reader(file, parse_mode)?;
+        parser.file_path = Some(file_path.to_string());
+        Ok(parser)
+    }
+
+    /// Creates a new `MavLogParser` from an arbitrary reader.
+    ///
+    /// This is the generic entry point behind `new`: it lets the log be parsed straight
+    /// from a `Cursor<Vec<u8>>`, a decompressed stream, or a network socket, without
+    /// requiring a real file on disk.
+    ///
+    /// Automatically detects the log file format and initializes the appropriate parser.
+    ///
+    /// # Arguments
+    ///
+    /// - `reader`: The source the log is read from.
+    /// - `parse_mode`: How tolerant the parser should be of corrupted framing.
+    ///   Defaults to `ParseMode::Lenient` when `None`, preserving the original behavior.
+    ///
+    /// # Returns
+    ///
+    /// An instance of `MavLogParser` initialized with the appropriate parser.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if the file header could not be read, e.g. because
+    /// the reader is truncated before the end of the 108-byte header, or because the
+    /// header is structurally invalid (unsupported format or MAVLink version).
+    pub fn from_reader<R: Read + 'static>(
+        reader: R,
+        parse_mode: Option<ParseMode>,
+    ) -> Result<Self, MessageReadError> {
+        let mut reader: PeekReader<R> = PeekReader::new(reader);
 
-        let header = Self::read_file_header(&mut reader);
+        let mut header = Self::read_file_header(&mut reader)?;
+        let dialect = header.message_definition.resolved_dialect.take();
 
-        let mav_version = Self::determine_mavlink_version(&header);
+        let mav_version = Self::determine_mavlink_version(&header)?;
+        let parse_mode = parse_mode.unwrap_or_default();
+        let recovery_stats = Rc::new(RefCell::new(RecoveryStats::default()));
+        let format_flags = header.format_flags;
+        let header_len = 108 + header.message_definition.size as u64;
+
+        let parser = Self::build_parser(reader, format_flags, mav_version, parse_mode, recovery_stats.clone());
+
+        Ok(MavLogParser {
+            parser,
+            dialect,
+            recovery_stats,
+            file_path: None,
+            uuid: header.uuid,
+            format_flags,
+            mav_version,
+            parse_mode,
+            header_len,
+            index: None,
+        })
+    }
 
-        let parser: Box<dyn MavParser<M = M>> = if header.format_flags.mavlink_only {
-            if header.format_flags.no_timestamp {
+    /// Builds the sub-parser matching `format_flags`, wrapping `reader` in a fresh
+    /// `PeekReader` positioned wherever `reader` currently is.
+    ///
+    /// Shared by `from_reader` (positioned right after the file header) and
+    /// `seek_to_offset` (positioned at an indexed entry boundary), so both paths
+    /// dispatch on format identically.
+    fn build_parser<R: Read + 'static>(
+        reader: PeekReader<R>,
+        format_flags: FormatFlags,
+        mav_version: MavlinkVersion,
+        parse_mode: ParseMode,
+        recovery_stats: Rc<RefCell<RecoveryStats>>,
+    ) -> Box<dyn MavParser<M = M>> {
+        if format_flags.mavlink_only {
+            if format_flags.not_timestamped {
                 Box::new(MavlinkOnlyNoTimestampParser {
                     reader,
                     mav_version,
@@ -269,66 +841,269 @@ impl<M: Message + 'static> MavLogParser<M> {
                 Box::new(TimestampedMavlinkOnlyParser {
                     reader,
                     mav_version,
+                    parse_mode,
+                    offset: 0,
+                    last_timestamp: None,
+                    recovery_stats,
                     _phantom: std::marker::PhantomData,
                 })
             }
         } else {
             Box::new(MixedParser {
-                timestamped: !header.format_flags.no_timestamp,
+                timestamped: !format_flags.not_timestamped,
                 reader,
                 mav_version,
+                parse_mode,
+                offset: 0,
+                last_timestamp: None,
+                recovery_stats,
                 _phantom: std::marker::PhantomData,
             })
-        };
+        }
+    }
+
+    /// The dialect resolved from the file header's embedded or linked message
+    /// definitions, if it carried one (`MavlinkDefinitionPayloadType::None` otherwise).
+    ///
+    /// Decoding still runs against the statically compiled `M: Message`; this is
+    /// exposed so callers can inspect or cross-check the dialect the log itself
+    /// recorded.
+    pub fn dialect(&self) -> Option<&dialect::Dialect> {
+        self.dialect.as_ref()
+    }
+
+    /// The bytes skipped and resyncs performed so far while recovering from
+    /// corrupted or truncated records under `ParseMode::Recover`. Stays zeroed
+    /// under any other `ParseMode`.
+    pub fn recovery_stats(&self) -> RecoveryStats {
+        *self.recovery_stats.borrow()
+    }
 
-        MavLogParser { parser }
+    /// Builds (or loads, if a sidecar from a prior run matches) the timestamp index
+    /// `seek_to_timestamp` and `seek_to_entry` need, without repositioning this
+    /// parser. A no-op if the index is already built.
+    ///
+    /// Index construction is otherwise lazy: callers that only ever read forward
+    /// never pay for it. `seek_to_timestamp`/`seek_to_entry` call this themselves,
+    /// so using those directly is fine too; this exists for callers that want to
+    /// pay the indexing cost up front, e.g. before presenting a scrubber UI.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if this parser wasn't constructed from a file
+    /// path (`new`, not `from_reader`), since indexing needs to reopen and scan the
+    /// file independently of this parser's own read position.
+    pub fn build_index(&mut self) -> Result<(), MessageReadError> {
+        if self.index.is_some() {
+            return Ok(());
+        }
+        let file_path = self.file_path.clone().ok_or_else(Self::seeking_unsupported_error)?;
+        if let Some(index) = TimestampIndex::load_sidecar(&file_path, self.uuid) {
+            self.index = Some(index);
+            return Ok(());
+        }
+        let index = Self::scan_index(&file_path, self.parse_mode)?;
+        index.save_sidecar(&file_path, self.uuid);
+        self.index = Some(index);
+        Ok(())
+    }
+
+    /// Repositions this parser so the next `next()` call returns the first entry
+    /// timestamped at or after `us`, building the index first if it isn't already.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if this parser wasn't constructed from a file
+    /// path, no indexed entry carries a timestamp (the `mavlink_only_no_timestamp`
+    /// case -- use `seek_to_entry` instead), or none is timestamped at or after `us`.
+    pub fn seek_to_timestamp(&mut self, us: u64) -> Result<(), MessageReadError> {
+        self.build_index()?;
+        let index = self.index.as_ref().expect("just built above");
+        let search_index = index.by_time.partition_point(|(timestamp, _)| *timestamp < us);
+        let &(_, entry_index) = index.by_time.get(search_index).ok_or_else(|| {
+            MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("no indexed entry timestamped at or after {us}us"),
+            ))
+        })?;
+        let offset = index.offsets[entry_index];
+        self.seek_to_offset(offset)
+    }
+
+    /// Repositions this parser so the next `next()` call returns the entry at
+    /// `entry_index` (0-based, in file order), building the index first if it
+    /// isn't already. This is the fallback for logs with no timestamps to index
+    /// by, but works for any log.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if this parser wasn't constructed from a file
+    /// path, or `entry_index` is past the last indexed entry.
+    pub fn seek_to_entry(&mut self, entry_index: u64) -> Result<(), MessageReadError> {
+        self.build_index()?;
+        let index = self.index.as_ref().expect("just built above");
+        let &offset = index.offsets.get(entry_index as usize).ok_or_else(|| {
+            MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("no indexed entry at index {entry_index}"),
+            ))
+        })?;
+        self.seek_to_offset(offset)
+    }
+
+    /// Repositions this parser so the next `next()` call reads starting from byte
+    /// `offset` into the file, re-dispatching on this file's format the same way
+    /// `from_reader` did, the same `ParseMode` this parser was constructed with.
+    ///
+    /// `offset` is expected to already be a valid entry boundary, e.g. one read
+    /// from `build_index`'s index; an arbitrary offset will likely desync the
+    /// first read back.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if this parser wasn't constructed from a file
+    /// path, or the file could not be reopened or seeked.
+    pub fn seek_to_offset(&mut self, offset: u64) -> Result<(), MessageReadError> {
+        let file_path = self.file_path.clone().ok_or_else(Self::seeking_unsupported_error)?;
+        let mut file = File::open(&file_path).map_err(MessageReadError::Io)?;
+        file.seek(SeekFrom::Start(offset)).map_err(MessageReadError::Io)?;
+        self.parser = Self::build_parser(
+            PeekReader::new(file),
+            self.format_flags,
+            self.mav_version,
+            self.parse_mode,
+            self.recovery_stats.clone(),
+        );
+        Ok(())
+    }
+
+    fn seeking_unsupported_error() -> MessageReadError {
+        MessageReadError::Io(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "seeking requires a MavLogParser opened from a file path (MavLogParser::new), \
+             not an arbitrary reader (MavLogParser::from_reader)",
+        ))
+    }
+
+    /// Builds a `TimestampIndex` by running a throwaway `MavLogParser` for this
+    /// file, recording each entry's offset (and timestamp, if it has one) as it's
+    /// read, rather than duplicating every format's framing logic here.
+    ///
+    /// Offsets are computed from `header_len` plus each entry's own
+    /// `index::entry_wire_size`, not from how many bytes the underlying reader
+    /// pulled off the file: `PeekReader` reads ahead into its own internal buffer,
+    /// so counting raw `Read` calls overshoots true entry boundaries by however much
+    /// read-ahead it did. Re-deriving each entry's length from its decoded fields
+    /// sidesteps that entirely, mirroring `tlog_parser::read_tlog_record`'s
+    /// reserialize-to-measure-length technique. `ParseMode::Recover`'s resync skips
+    /// are accounted for separately via the delta in `recovery_stats().skipped_bytes`
+    /// across the call, since those bytes never make it into the returned entry.
+    fn scan_index(file_path: &str, parse_mode: ParseMode) -> Result<TimestampIndex, MessageReadError> {
+        let file = File::open(file_path).map_err(MessageReadError::Io)?;
+        let mut parser = Self::from_reader(file, Some(parse_mode))?;
+
+        let mut offset = parser.header_len;
+        let mut offsets = Vec::new();
+        let mut by_time = Vec::new();
+        loop {
+            let skipped_before = parser.recovery_stats().skipped_bytes;
+            match parser.next() {
+                Ok(entry) => {
+                    let skipped_delta = parser.recovery_stats().skipped_bytes - skipped_before;
+                    let index = offsets.len();
+                    if let Some(timestamp) = entry.timestamp {
+                        by_time.push((timestamp, index));
+                    }
+                    offsets.push(offset);
+                    offset += skipped_delta
+                        + index::entry_wire_size(&entry, parser.format_flags, parser.mav_version);
+                }
+                Err(_) => break,
+            }
+        }
+        by_time.sort_by_key(|(timestamp, _)| *timestamp);
+        Ok(TimestampIndex { offsets, by_time })
+    }
+
+    /// Decodes a raw MAVLink frame against the dialect resolved from this log's
+    /// header, for a message id the compiled `M: Message` doesn't know how to
+    /// decode. Returns `None` if the header carried no dialect.
+    ///
+    /// `raw` is a complete v1 or v2 frame, the format `TlogParser` and
+    /// `MavLogWriter` round-trip through `LogEntry::raw`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `dialect::DialectError` if `raw` isn't a well-formed frame, or
+    /// names a message id the resolved dialect has no definition for.
+    pub fn decode_dynamic(
+        &self,
+        raw: &[u8],
+    ) -> Option<Result<dynamic::DynamicMessage, dialect::DialectError>> {
+        self.dialect.as_ref().map(|dialect| dialect.decode_frame(raw))
     }
 
     /// Reads the file header to extract metadata and format information.
     ///
     /// # Arguments
-    /// - `reader`: A `PeekReader` for the log file.
+    /// - `reader`: A `PeekReader` for the log source.
     ///
     /// # Returns
     /// A `FileHeader` containing metadata about the log file.
     ///
-    /// # Panics
-    ///
-    /// Panics if the file header is corrupted or if the format is unsupported since that makes
-    /// it impossible to guarantee correct parsing.
+    /// # Errors
     ///
-    fn read_file_header(reader: &mut PeekReader<File>) -> FileHeader {
-        let header_bytes: [u8; 108] = reader
-            .read_exact(108)
-            .expect("Failed to read file header.")
-            .try_into()
-            .expect("Failed to read file header.");
+    /// Returns a `MessageReadError` if the underlying reader does not have at least
+    /// 108 bytes available (e.g. a truncated log file), if the header's declared
+    /// message-definition size runs past the end of the reader, if the header names
+    /// an embedded or linked dialect definition that can't be resolved (e.g.
+    /// truncated or otherwise malformed), or if the header names an unsupported
+    /// format version.
+    fn read_file_header<R: Read>(reader: &mut PeekReader<R>) -> Result<FileHeader, MessageReadError> {
+        let header_bytes: [u8; 108] = reader.read_exact(108)?.try_into().map_err(|_| {
+            MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "file header did not contain the expected 108 bytes",
+            ))
+        })?;
         let mut header = FileHeader::unpack(&header_bytes);
         if header.message_definition.payload_type != MavlinkDefinitionPayloadType::None {
             let definitions_raw: &[u8] = reader
                 .read_exact(header.message_definition.size as usize)
-                .expect("Failed to read message definitions.");
+                .map_err(|_| {
+                    MessageReadError::Io(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "file header did not contain the declared message definitions",
+                    ))
+                })?;
             header.message_definition.unpack_payload(definitions_raw);
         } else {
             header.message_definition.size = 0;
         }
 
-        match header.message_definition.payload_type {
-            MavlinkDefinitionPayloadType::None => {}
-            MavlinkDefinitionPayloadType::Utf8SpaceDelimitedUrlsForXMLFiles => {
-                panic!("Custom XML files for message definitions are not supported.");
-            }
-            MavlinkDefinitionPayloadType::Utf8Xml => {
-                panic!("XML for message definitions is not supported.");
-            }
+        if header.message_definition.payload_type != MavlinkDefinitionPayloadType::None {
+            let resolved = dialect::MessageDefinitionResolver::new()
+                .resolve(&header.message_definition)
+                .map_err(|err| {
+                    MessageReadError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("failed to resolve dialect definitions: {err}"),
+                    ))
+                })?;
+            header.message_definition.resolved_dialect = Some(resolved);
         }
 
         match header.format_version {
             1 => {}
-            _ => panic!("Unsupported file format version."),
+            other => {
+                return Err(MessageReadError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("unsupported file format version: {other}"),
+                )))
+            }
         }
 
-        header
+        Ok(header)
     }
 
     /// Determines the MAVLink version based on the file header.
@@ -339,15 +1114,18 @@ impl<M: Message + 'static> MavLogParser<M> {
     /// # Returns
     /// The MAVLink version (`V1` or `V2`).
     ///
-    /// # Panics
-    ///
-    /// Panics if the MAVLink version is unsupported.
+    /// # Errors
     ///
-    fn determine_mavlink_version(header: &FileHeader) -> MavlinkVersion {
+    /// Returns a `MessageReadError` if the header names an unsupported MAVLink
+    /// version.
+    fn determine_mavlink_version(header: &FileHeader) -> Result<MavlinkVersion, MessageReadError> {
         match header.message_definition.version_major {
-            2 => MavlinkVersion::V2,
-            1 => MavlinkVersion::V1,
-            _ => panic!("Unsupported MAVLink version."),
+            2 => Ok(MavlinkVersion::V2),
+            1 => Ok(MavlinkVersion::V1),
+            other => Err(MessageReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported MAVLink version: {other}"),
+            ))),
         }
     }
 }
@@ -366,4 +1144,14 @@ impl<M: Message + 'static> MavParser for MavLogParser<M> {
     fn next(&mut self) -> Result<LogEntry<M>, MessageReadError> {
         self.parser.next()
     }
+
+    /// Delegates to the underlying parser selected during initialization.
+    fn peek_frame(&mut self) -> Result<Option<PeekedFrame>, MessageReadError> {
+        self.parser.peek_frame()
+    }
+
+    /// Delegates to the underlying parser selected during initialization.
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        self.parser.skip_one()
+    }
 }
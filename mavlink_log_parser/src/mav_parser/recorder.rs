@@ -0,0 +1,72 @@
+//! Bridges a live `mavlink::MavConnection` into the writer side of this crate, so a
+//! ground station can persist everything it receives from a vehicle without
+//! hand-rolling the framing `MavLogWriter` already knows.
+
+use std::io::Write;
+use std::time::Instant;
+
+use mavlink::error::MessageReadError;
+use mavlink::{MavConnection, MavHeader, MavlinkVersion, Message};
+
+use super::header::FileHeader;
+use super::writer::MavLogWriter;
+
+/// Records every message received from a live `MavConnection` into a `MavLogWriter`,
+/// stamping each with a monotonic capture timestamp rather than anything the
+/// vehicle itself reports, since the point of recording is to capture *when this
+/// ground station saw it*, not to trust the vehicle's own clock.
+pub struct ConnectionRecorder<M: Message, W: Write> {
+    connection: Box<dyn MavConnection<M> + Send + Sync>,
+    writer: MavLogWriter<M, W>,
+    started_at: Instant,
+}
+
+impl<M: Message, W: Write> ConnectionRecorder<M, W> {
+    /// Creates a recorder over `connection`, writing `header` to `writer` immediately
+    /// via `MavLogWriter::new`.
+    ///
+    /// `header.message_definition.version_major` is overwritten with
+    /// `connection.get_protocol_version()` before it's written, so the embedded
+    /// definitions header always reflects the MAVLink version the connection is
+    /// actually negotiated to, regardless of what `header` was constructed with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the header could not be written to `writer`.
+    pub fn new(
+        connection: Box<dyn MavConnection<M> + Send + Sync>,
+        writer: W,
+        mut header: FileHeader,
+    ) -> std::io::Result<Self> {
+        header.message_definition.version_major = match connection.get_protocol_version() {
+            MavlinkVersion::V1 => 1,
+            MavlinkVersion::V2 => 2,
+        };
+        let writer = MavLogWriter::new(writer, header)?;
+        Ok(Self {
+            connection,
+            writer,
+            started_at: Instant::now(),
+        })
+    }
+
+    /// Blocks for the next message on the connection, records it, and returns it to
+    /// the caller -- "passthrough+record" in one call, so a ground station can
+    /// display and log in one pass instead of receiving twice.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageReadError` if the connection failed to receive a message,
+    /// or wraps an `io::Error` in `MessageReadError::Io` if the received message
+    /// could not be persisted to the log; either way the message is not returned
+    /// to the caller, since a caller receiving a message it was told failed to
+    /// record would have no way to tell the two failure modes apart.
+    pub fn record_next(&mut self) -> Result<(MavHeader, M), MessageReadError> {
+        let (header, message) = self.connection.recv()?;
+        let timestamp_us = self.started_at.elapsed().as_micros() as u64;
+        self.writer
+            .write_mavlink(header, &message, timestamp_us)
+            .map_err(MessageReadError::Io)?;
+        Ok((header, message))
+    }
+}
@@ -0,0 +1,174 @@
+//! Writer counterpart to the parsers in `mav_parser`, producing logs in exactly the
+//! layouts `MavlinkOnlyNoTimestampParser`, `TimestampedMavlinkOnlyParser`, and
+//! `MixedParser` know how to read back: the same `FileHeader`, the same `EntryType`
+//! tag byte, the same optional 8-byte little-endian timestamp, and the same
+//! `payload_size` framing.
+
+use std::io::Write;
+use std::marker::PhantomData;
+
+use mavlink::{MAVLinkV1MessageRaw, MAVLinkV2MessageRaw, MavHeader, MavlinkVersion, Message};
+
+use super::header::FileHeader;
+use super::EntryType;
+use crate::LogEntry;
+
+/// Writes MAVLink log entries to `W` in the format described by a `FileHeader`.
+///
+/// Generic over the underlying sink `W`, so the same framing logic can target a
+/// plain file, an in-memory buffer, or any other `Write` destination.
+pub struct MavLogWriter<M: Message, W: Write> {
+    writer: W,
+    header: FileHeader,
+    mav_version: MavlinkVersion,
+    _phantom: PhantomData<M>,
+}
+
+impl<M: Message, W: Write> MavLogWriter<M, W> {
+    /// Creates a new `MavLogWriter`, writing `header` (and its message definition
+    /// payload, if any) to `writer` immediately.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer`: The destination entries are framed and written into.
+    /// - `header`: The file header to write. Its `format_flags` control how
+    ///   subsequent entries are framed, and `message_definition.version_major`
+    ///   determines the MAVLink version used to serialize `write_mavlink` entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the header (or its message definition payload)
+    /// could not be written.
+    pub fn new(mut writer: W, header: FileHeader) -> std::io::Result<Self> {
+        writer.write_all(&header.pack())?;
+        if let Some(payload) = &header.message_definition.payload {
+            writer.write_all(payload)?;
+        }
+        let mav_version = match header.message_definition.version_major {
+            2 => MavlinkVersion::V2,
+            _ => MavlinkVersion::V1,
+        };
+        Ok(Self {
+            writer,
+            header,
+            mav_version,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Writes a MAVLink message entry timestamped `timestamp_us`.
+    ///
+    /// `timestamp_us` is ignored if the header's `format_flags.not_timestamped` is
+    /// set, since the log format then has no field to hold it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the entry could not be written.
+    pub fn write_mavlink(
+        &mut self,
+        mav_header: MavHeader,
+        msg: &M,
+        timestamp_us: u64,
+    ) -> std::io::Result<()> {
+        match self.mav_version {
+            MavlinkVersion::V1 => {
+                let mut raw = MAVLinkV1MessageRaw::new();
+                raw.serialize_message(mav_header, msg);
+                self.frame_and_write(EntryType::Mavlink, raw.raw_bytes(), timestamp_us)
+            }
+            MavlinkVersion::V2 => {
+                let mut raw = MAVLinkV2MessageRaw::new();
+                raw.serialize_message(mav_header, msg);
+                self.frame_and_write(EntryType::Mavlink, raw.raw_bytes(), timestamp_us)
+            }
+        }
+    }
+
+    /// Writes a raw binary entry timestamped `timestamp_us`.
+    ///
+    /// `timestamp_us` is ignored if the header's `format_flags.not_timestamped` is
+    /// set, since the log format then has no field to hold it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the entry could not be written, or if the header's
+    /// `format_flags.mavlink_only` is set, since a mavlink-only log has no framing
+    /// for non-MAVLink entries.
+    pub fn write_raw(&mut self, data: &[u8], timestamp_us: u64) -> std::io::Result<()> {
+        self.frame_and_write(EntryType::Raw, data, timestamp_us)
+    }
+
+    /// Writes a UTF-8 text entry timestamped `timestamp_us`.
+    ///
+    /// `timestamp_us` is ignored if the header's `format_flags.not_timestamped` is
+    /// set, since the log format then has no field to hold it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the entry could not be written, or if the header's
+    /// `format_flags.mavlink_only` is set, since a mavlink-only log has no framing
+    /// for non-MAVLink entries.
+    pub fn write_text(&mut self, text: &str, timestamp_us: u64) -> std::io::Result<()> {
+        self.frame_and_write(EntryType::Utf8Text, text.as_bytes(), timestamp_us)
+    }
+
+    /// Writes a single `LogEntry`, dispatching to `write_mavlink`, `write_raw`, or
+    /// `write_text` depending on which of its fields is populated, so an entry
+    /// produced by `MavLogParser` can be written back out without the caller having
+    /// to pick the right method itself.
+    ///
+    /// Uses `entry.timestamp`, defaulting to `0` if it's `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the entry could not be written, or if `entry` has
+    /// none of `mav_header`/`mav_message`, `raw`, or `text` populated.
+    pub fn write_entry(&mut self, entry: &LogEntry<M>) -> std::io::Result<()> {
+        let timestamp_us = entry.timestamp.unwrap_or(0);
+        if let (Some(mav_header), Some(message)) = (entry.mav_header, &entry.mav_message) {
+            self.write_mavlink(mav_header, message, timestamp_us)
+        } else if let Some(raw) = &entry.raw {
+            self.write_raw(raw, timestamp_us)
+        } else if let Some(text) = &entry.text {
+            self.write_text(text, timestamp_us)
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "LogEntry has no mav_message, raw, or text payload to write",
+            ))
+        }
+    }
+
+    /// Frames and writes a single entry according to `header.format_flags`.
+    fn frame_and_write(
+        &mut self,
+        entry_type: EntryType,
+        data: &[u8],
+        timestamp_us: u64,
+    ) -> std::io::Result<()> {
+        if self.header.format_flags.mavlink_only && entry_type != EntryType::Mavlink {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "mavlink_only log files cannot contain non-MAVLink entries",
+            ));
+        }
+
+        if self.header.format_flags.mavlink_only {
+            // Frames are self-delimited by their own v1/v2 header and CRC, so there is
+            // no type byte and no size field, mirroring MavlinkOnlyNoTimestampParser
+            // and TimestampedMavlinkOnlyParser.
+            if !self.header.format_flags.not_timestamped {
+                self.writer.write_all(&timestamp_us.to_le_bytes())?;
+            }
+            self.writer.write_all(data)
+        } else {
+            self.writer.write_all(&(entry_type as u8).to_le_bytes())?;
+            if !self.header.format_flags.not_timestamped {
+                self.writer.write_all(&timestamp_us.to_le_bytes())?;
+            }
+            self.writer
+                .write_all(&(data.len() as u16).to_le_bytes())?;
+            self.writer.write_all(data)
+        }
+    }
+}
@@ -3,65 +3,283 @@
 /// implements the `MavParser` trait to read and process MAVLink messages
 /// from a TLOG file.
 /// See /docs/tlog_file_format.md for more information on the TLOG file format.
+use std::fs::File;
+use std::io::{BufReader, Read};
+
 use mavlink::error::MessageReadError;
-use mavlink::{MavConnection, Message};
+use mavlink::peek_reader::PeekReader;
+use mavlink::{read_versioned_msg, MAVLinkV1MessageRaw, MAVLinkV2MessageRaw, MavlinkVersion, Message};
 
+use crate::filter::PeekedFrame;
 use crate::LogEntry;
 use crate::MavParser;
 
-/// A parser for telemetry log (TLOG) files that uses the MAVLink protocol.
+/// The header fields of a self-delimited MAVLink v1/v2 frame, readable
+/// straight off its wire bytes without decoding the rest of the frame.
+struct PeekedFrameHeader {
+    /// Total on-wire length of the frame (header + payload + CRC + optional
+    /// v2 signature).
+    frame_len: usize,
+    message_id: u32,
+    system_id: u8,
+    component_id: u8,
+}
+
+/// The number of a frame's leading bytes `parse_mav_frame_header` needs
+/// peeked before it can read the frame's length and ids.
+fn mav_frame_header_len(mav_version: MavlinkVersion) -> usize {
+    match mav_version {
+        MavlinkVersion::V1 => 6,
+        MavlinkVersion::V2 => 10,
+    }
+}
+
+/// Reads a self-delimited MAVLink v1/v2 frame's header fields straight off its
+/// wire bytes, without decoding the payload. `header` must hold at least
+/// `mav_frame_header_len(mav_version)` bytes already peeked from the frame's
+/// start byte; this never touches a reader itself.
+fn parse_mav_frame_header(header: &[u8], mav_version: MavlinkVersion) -> PeekedFrameHeader {
+    match mav_version {
+        MavlinkVersion::V1 => {
+            // STX(1) LEN(1) SEQ(1) SYSID(1) COMPID(1) MSGID(1) PAYLOAD(LEN) CRC(2)
+            let payload_len = header[1] as usize;
+            PeekedFrameHeader {
+                frame_len: 6 + payload_len + 2,
+                message_id: header[5] as u32,
+                system_id: header[3],
+                component_id: header[4],
+            }
+        }
+        MavlinkVersion::V2 => {
+            // STX(1) LEN(1) INCOMPAT(1) COMPAT(1) SEQ(1) SYSID(1) COMPID(1)
+            // MSGID(3) PAYLOAD(LEN) CRC(2) [SIGNATURE(13) if INCOMPAT & 0x01]
+            let payload_len = header[1] as usize;
+            let signed = header[2] & 0x01 != 0;
+            PeekedFrameHeader {
+                frame_len: 10 + payload_len + 2 + if signed { 13 } else { 0 },
+                message_id: u32::from_le_bytes([header[7], header[8], header[9], 0]),
+                system_id: header[5],
+                component_id: header[6],
+            }
+        }
+    }
+}
+
+/// Which MAVLink version the byte at a record's frame-start position
+/// indicates, honoring `forced_version` the same way `read_tlog_record` does.
+/// `None` means the byte matches neither start-of-frame marker (or not the
+/// forced one), so only `read_tlog_record`'s resync scan can make progress.
+fn peeked_frame_version(start_byte: u8, forced_version: Option<MavlinkVersion>) -> Option<MavlinkVersion> {
+    match start_byte {
+        mavlink::MAV_STX if !matches!(forced_version, Some(MavlinkVersion::V2)) => Some(MavlinkVersion::V1),
+        mavlink::MAV_STX_V2 if !matches!(forced_version, Some(MavlinkVersion::V1)) => Some(MavlinkVersion::V2),
+        _ => None,
+    }
+}
+
+/// Reads one timestamped record from a TLOG stream, mirroring `TlogParser::next`'s
+/// resync behavior, and returns it alongside the number of bytes consumed from
+/// `reader` so callers that need byte offsets (e.g. `LogIndex`) can track them
+/// without duplicating this decode loop.
+///
+/// `forced_version`, if set, restricts both the resync scan and decoding to that
+/// single MAVLink version, treating the other version's start byte as just more
+/// corrupt data to scan past. If `None`, either start byte is accepted and the
+/// version is chosen per-record, so a single log mixing v1 and v2 frames parses
+/// cleanly.
+pub(crate) fn read_tlog_record<M: Message, R: Read>(
+    reader: &mut PeekReader<R>,
+    forced_version: Option<MavlinkVersion>,
+) -> Result<(LogEntry<M>, u64), MessageReadError> {
+    let timestamp_raw = reader.read_exact(8)?;
+    let mut timestamp_us = Some(u64::from_be_bytes(
+        timestamp_raw
+            .try_into()
+            .expect("read_exact(8) always returns exactly 8 bytes"),
+    ));
+    let mut bytes_consumed: u64 = 8;
+
+    loop {
+        let start_byte = reader.peek_exact(1)?[0];
+        let mav_version = match start_byte {
+            mavlink::MAV_STX if !matches!(forced_version, Some(MavlinkVersion::V2)) => {
+                MavlinkVersion::V1
+            }
+            mavlink::MAV_STX_V2 if !matches!(forced_version, Some(MavlinkVersion::V1)) => {
+                MavlinkVersion::V2
+            }
+            _ => {
+                reader.read_u8()?;
+                bytes_consumed += 1;
+                timestamp_us = None;
+                continue;
+            }
+        };
+
+        let (header, message) = read_versioned_msg::<M, R>(reader, mav_version)?;
+
+        let raw = match mav_version {
+            MavlinkVersion::V1 => {
+                let mut frame = MAVLinkV1MessageRaw::new();
+                frame.serialize_message(header, &message);
+                frame.raw_bytes().to_vec()
+            }
+            MavlinkVersion::V2 => {
+                let mut frame = MAVLinkV2MessageRaw::new();
+                frame.serialize_message(header, &message);
+                frame.raw_bytes().to_vec()
+            }
+        };
+        bytes_consumed += raw.len() as u64;
+
+        return Ok((
+            LogEntry {
+                timestamp: timestamp_us,
+                mav_header: Some(header),
+                mav_message: Some(message),
+                mav_version: Some(mav_version),
+                text: None,
+                raw: Some(raw),
+            },
+            bytes_consumed,
+        ));
+    }
+}
+
+/// A parser for telemetry log (TLOG) files.
+///
+/// Each record in a TLOG file is an 8-byte big-endian microsecond-since-Unix-epoch
+/// timestamp immediately followed by a single MAVLink v1 or v2 frame. `TlogParser`
+/// reads this layout natively over a buffered file, rather than going through a
+/// `mavlink::MavConnection`, so it can recover the per-message timestamp that a
+/// `file:` connection discards.
 ///
-/// The `TlogParser` struct wraps a MAVLink connection to read messages
-/// from a TLOG file. It implements the `MavParser` trait, allowing it to
-/// process MAVLink messages and return them as `LogEntry` objects.
+/// By default (`new`) it auto-detects each record's MAVLink version from its start
+/// byte, so a single log mixing v1 and v2 frames parses cleanly; `with_version`
+/// instead fixes one version for the whole file. Either way, the version used to
+/// decode a given record is surfaced on its `LogEntry::mav_version`.
 ///
 /// # Type Parameters
 /// - `M`: The type of MAVLink message being parsed.
 pub struct TlogParser<M: Message> {
-    /// A boxed MAVLink connection object for reading messages from the TLOG file.
-    file_conn: Box<dyn MavConnection<M> + Sync + Send>,
+    reader: PeekReader<BufReader<File>>,
+    forced_version: Option<MavlinkVersion>,
+    _phantom: std::marker::PhantomData<M>,
 }
 
 impl<M: Message> TlogParser<M> {
-    /// Creates a new `TlogParser` instance for the specified TLOG file path.
+    /// Creates a new `TlogParser` instance for the specified TLOG file path,
+    /// auto-detecting each record's MAVLink version from its start byte.
     ///
     /// # Arguments
     /// - `file_path`: The path to the TLOG file to be parsed.
     ///
     /// # Panics
-    /// This function will panic if the provided file path is invalid or if
-    /// the connection to the TLOG file cannot be established.
+    /// This function will panic if the provided file path is invalid.
     ///
     pub fn new(file_path: &str) -> Self {
-        let connection_string = format!("file:{}", file_path);
-        let vehicle =
-            mavlink::connect::<M>(&connection_string).expect("An invalid file path was provided");
-        Self { file_conn: vehicle }
+        Self::new_with(file_path, None)
+    }
+
+    /// Creates a new `TlogParser` that decodes every record as `version`,
+    /// instead of auto-detecting it per-record. Useful when the log's version
+    /// is already known, or to keep a resync scan from treating an occasional
+    /// stray byte of the other version's magic number as a valid frame start.
+    ///
+    /// # Arguments
+    /// - `file_path`: The path to the TLOG file to be parsed.
+    /// - `version`: The MAVLink version to decode every record as.
+    ///
+    /// # Panics
+    /// This function will panic if the provided file path is invalid.
+    pub fn with_version(file_path: &str, version: MavlinkVersion) -> Self {
+        Self::new_with(file_path, Some(version))
+    }
+
+    fn new_with(file_path: &str, forced_version: Option<MavlinkVersion>) -> Self {
+        let file = File::open(file_path).expect("An invalid file path was provided");
+        Self {
+            reader: PeekReader::new(BufReader::new(file)),
+            forced_version,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a new `TlogParser` positioned at `offset` bytes into the file at
+    /// `file_path`, for resuming at a record boundary found by `LogIndex`.
+    ///
+    /// # Panics
+    /// Panics if the file path is invalid or `offset` could not be seeked to.
+    pub(crate) fn new_at_offset(file_path: &str, offset: u64) -> Self {
+        use std::io::{Seek, SeekFrom};
+
+        let mut file = File::open(file_path).expect("An invalid file path was provided");
+        file.seek(SeekFrom::Start(offset))
+            .expect("Failed to seek to the requested offset");
+        Self {
+            reader: PeekReader::new(BufReader::new(file)),
+            forced_version: None,
+            _phantom: std::marker::PhantomData,
+        }
     }
 }
 
 impl<M: Message> MavParser for TlogParser<M> {
     type M = M;
 
-    /// Reads the next MAVLink message from the TLOG file and returns it as a `LogEntry`.
+    /// Reads the next timestamped MAVLink record from the TLOG file.
     ///
-    /// # Returns
-    /// - `Ok(LogEntry)`: If a message is successfully read from the TLOG file.
-    /// - `Err(MessageReadError)`: If an error occurs while reading the message.
+    /// Reads the 8-byte big-endian timestamp that precedes every record, then peeks
+    /// the following byte to tell a v1 frame (`0xFE`) from a v2 frame (`0xFD`) before
+    /// decoding it with `read_versioned_msg`. If that byte is neither (or doesn't
+    /// match `with_version`'s forced version), the timestamp just read can no longer
+    /// be trusted either, so it's discarded and reading resumes one byte further in,
+    /// scanning forward until a recognized frame start is found again.
     ///
-    /// The `LogEntry` contains the MAVLink message, its header, and optional
-    /// metadata such as a timestamp or raw data.
+    /// # Returns
+    /// - `Ok(LogEntry)`: If a message is successfully read from the TLOG file. Its
+    ///   `raw` field holds the frame re-serialized from the decoded header and
+    ///   message, which is byte-identical to what was written to the log, and its
+    ///   `mav_version` holds the version it was decoded as.
+    /// - `Err(MessageReadError)`: If the file is exhausted (clean EOF before the
+    ///   next record) or the underlying frame cannot be decoded.
     ///
     fn next(&mut self) -> Result<LogEntry<Self::M>, MessageReadError> {
-        match self.file_conn.recv() {
-            Ok((header, msg)) => Ok(LogEntry {
-                timestamp: None,
-                mav_header: Some(header),
-                mav_message: Some(msg),
-                text: None,
-                raw: None,
-            }),
-            Err(err) => return Err(err),
-        }
+        read_tlog_record::<M, BufReader<File>>(&mut self.reader, self.forced_version)
+            .map(|(entry, _bytes_consumed)| entry)
+    }
+
+    /// Peeks the 8-byte timestamp and the frame-start byte right after it to
+    /// learn a record's MAVLink version, then its id/system id/component id
+    /// straight off its header, without decoding the frame. Returns `Ok(None)`
+    /// if that byte doesn't mark a frame start `read_tlog_record` would accept
+    /// (given `forced_version`), since only its resync scan can make progress
+    /// from there.
+    fn peek_frame(&mut self) -> Result<Option<PeekedFrame>, MessageReadError> {
+        let start_byte = self.reader.peek_exact(9)?[8];
+        let mav_version = match peeked_frame_version(start_byte, self.forced_version) {
+            Some(mav_version) => mav_version,
+            None => return Ok(None),
+        };
+        let peeked = self.reader.peek_exact(8 + mav_frame_header_len(mav_version))?;
+        let header = parse_mav_frame_header(&peeked[8..], mav_version);
+        Ok(Some(PeekedFrame {
+            message_id: header.message_id,
+            system_id: header.system_id,
+            component_id: header.component_id,
+        }))
+    }
+
+    fn skip_one(&mut self) -> Result<bool, MessageReadError> {
+        let start_byte = self.reader.peek_exact(9)?[8];
+        let mav_version = match peeked_frame_version(start_byte, self.forced_version) {
+            Some(mav_version) => mav_version,
+            None => return Ok(false),
+        };
+        let peeked = self.reader.peek_exact(8 + mav_frame_header_len(mav_version))?;
+        let frame_len = parse_mav_frame_header(&peeked[8..], mav_version).frame_len;
+        self.reader.read_exact(8)?;
+        self.reader.read_exact(frame_len)?;
+        Ok(true)
     }
 }
@@ -7,18 +7,28 @@ mod mav_parse_tests {
         GpsFixType, MavAutopilot, MavMessage, MavModeFlag, MavState, MavType, ATTITUDE_DATA,
         GPS2_RAW_DATA, HEARTBEAT_DATA,
     };
-    use mavlink::{MAVLinkV2MessageRaw, MavHeader};
-    use mavlink_log_parser::mav_parser::MavLogParser;
-    use mavlink_log_parser::MavParser;
+    use mavlink::{MAVLinkV2MessageRaw, MavHeader, Message};
+    use mavlink_log_parser::filter::{EntryKind, FilterSpec};
+    use mavlink_log_parser::mav_parser::dialect::Dialect;
+    use mavlink_log_parser::mav_parser::header::{
+        FileHeader, FormatFlags, MavlinkDefinitionPayloadType, MavlinkMessageDefinition,
+    };
+    use mavlink_log_parser::mav_parser::writer::MavLogWriter;
+    use mavlink_log_parser::mav_parser::{MavLogParser, ParseMode};
+    use mavlink_log_parser::{LogEntry, MavParser};
     use tempfile;
+    use uuid::Uuid;
 
     #[test]
-    #[should_panic(expected = "Failed to read file header.")]
     fn test_mav_log_parser_file_to_small_for_header() {
         // not enough data for header
         let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
         temp_file.write(&[0u8]).expect("Failed to write test file");
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        let result = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            None,
+        );
+        assert!(result.is_err(), "expected a truncated header to be reported as an error");
     }
 
     #[test]
@@ -47,7 +57,7 @@ mod mav_parse_tests {
             .write(&packed_data)
             .expect("Failed to write test file");
 
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap(), None);
     }
 
     #[test]
@@ -76,12 +86,14 @@ mod mav_parse_tests {
             .write(&packed_data)
             .expect("Failed to write test file");
 
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap(), None);
     }
 
     #[test]
-    #[should_panic(expected = "Custom XML files for message definitions are not supported.")]
-    fn test_mav_log_parser_file_unsupported_payload_type_urls() {
+    fn test_mav_log_parser_file_payload_type_urls_resolves_to_empty_dialect() {
+        // `MessageDefinitionResolver` now actually resolves this payload type instead
+        // of panicking on it; with `size: 0` there are no URLs to follow, so the
+        // result is a successfully-constructed parser with an empty dialect.
         let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
         let packed_data: [u8; 108] = [
             // file header
@@ -104,12 +116,18 @@ mod mav_parse_tests {
             .write(&packed_data)
             .expect("Failed to write test file");
 
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        let parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            None,
+        )
+        .expect("Failed to create parser");
+        assert_eq!(parser.dialect(), Some(&Dialect::default()));
     }
 
     #[test]
-    #[should_panic(expected = "XML for message definitions is not supported.")]
-    fn test_mav_log_parser_file_unsupported_payload_type_xml() {
+    fn test_mav_log_parser_file_payload_type_xml_resolves_to_empty_dialect() {
+        // Same as above, for the embedded-XML payload type: `size: 0` means there is
+        // no XML to parse, so resolution succeeds with an empty dialect.
         let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
         let packed_data: [u8; 108] = [
             // file header
@@ -132,7 +150,12 @@ mod mav_parse_tests {
             .write(&packed_data)
             .expect("Failed to write test file");
 
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        let parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            None,
+        )
+        .expect("Failed to create parser");
+        assert_eq!(parser.dialect(), Some(&Dialect::default()));
     }
 
     #[test]
@@ -160,7 +183,7 @@ mod mav_parse_tests {
             .write(&packed_data)
             .expect("Failed to write test file");
 
-        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap());
+        MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(temp_file.path().to_str().unwrap(), None);
     }
 
     #[test]
@@ -190,7 +213,8 @@ mod mav_parse_tests {
 
         let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
             temp_file.path().to_str().unwrap(),
-        );
+            None,
+        ).expect("Failed to create parser");
         for i in 0..60 {
             let entry = parser.next();
             assert!(entry.is_ok(), "{i} {:?}", entry.err());
@@ -228,7 +252,8 @@ mod mav_parse_tests {
 
         let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
             temp_file.path().to_str().unwrap(),
-        );
+            None,
+        ).expect("Failed to create parser");
         for i in 0..60 {
             let entry = parser.next();
             assert!(entry.is_ok(), "Iteration: {i} {:?}", entry.err());
@@ -270,7 +295,8 @@ mod mav_parse_tests {
 
         let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
             temp_file.path().to_str().unwrap(),
-        );
+            None,
+        ).expect("Failed to create parser");
         for i in 0..20 {
             // handle raw entry
             let entry = parser.next();
@@ -345,7 +371,8 @@ mod mav_parse_tests {
 
         let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
             temp_file.path().to_str().unwrap(),
-        );
+            None,
+        ).expect("Failed to create parser");
 
         // Check the first iteration
         let first_entry = parser.next();
@@ -377,6 +404,583 @@ mod mav_parse_tests {
         temp_file.close().unwrap();
     }
 
+    #[test]
+    fn test_mav_log_parser_sub_parser_strict_mode_rejects_missing_timestamp() {
+        // Same corrupted file as the lenient "missing timestamp" test above, but parsed
+        // in Strict mode, where this desync must be reported instead of silently
+        // falling through to read_versioned_msg's resync.
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+        let mut packed_data: Vec<u8> = vec![
+            // file header
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, // uuid
+            16, 0, 0, 0, 0, 0, 0, 17, // timestamp_us
+            b'a', b'p', b'p', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, // src_application_id
+            1, 0, 0, 0, // format_version
+            1, 0, // format_flags
+            // message_definition
+            2, 0, 0, 0, // version_major
+            1, 0, 0, 0, // version_minor
+            b't', b'e', b's', b't', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, // dialect
+            0, 0, // payload_type
+            0, 0, 0, 0, // size
+        ];
+        populate_data(true, true, &mut packed_data);
+
+        // Remove the timestamp data from packed_data, the same corruption the lenient
+        // test exercises.
+        packed_data.drain(108..116);
+
+        temp_file
+            .write(&packed_data)
+            .expect("Failed to write modified test file");
+
+        let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            Some(ParseMode::Strict),
+        ).expect("Failed to create parser");
+
+        let first_entry = parser.next();
+        assert!(
+            first_entry.is_err(),
+            "Strict mode should reject a frame that doesn't start where expected"
+        );
+        temp_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_mav_log_parser_sub_parser_recover_mode_resyncs_past_injected_garbage() {
+        // Same mavlink-only, timestamped layout as the other sub-parser tests, but with
+        // 3 garbage bytes spliced in right at the boundary between the first and second
+        // entries, simulating a few bytes of corruption. Recover mode should skip
+        // exactly those bytes and resume parsing from the real second entry, rather
+        // than reporting a desync (Strict) or silently misreading it (Lenient).
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+        let mut packed_data: Vec<u8> = vec![
+            // file header
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, // uuid
+            16, 0, 0, 0, 0, 0, 0, 17, // timestamp_us
+            b'a', b'p', b'p', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, // src_application_id
+            1, 0, 0, 0, // format_version
+            1, 0, // format_flags
+            // message_definition
+            2, 0, 0, 0, // version_major
+            1, 0, 0, 0, // version_minor
+            b't', b'e', b's', b't', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, // dialect
+            0, 0, // payload_type
+            0, 0, 0, 0, // size
+        ];
+        let header_len = packed_data.len();
+        populate_data(true, true, &mut packed_data);
+
+        // The first entry is an 8-byte timestamp followed by a serialized HEARTBEAT
+        // frame; probe a throwaway HEARTBEAT to learn that frame's length without
+        // hardcoding it, since it doesn't depend on the field values carried.
+        let mut probe = MAVLinkV2MessageRaw::new();
+        probe.serialize_message(
+            MavHeader {
+                sequence: 0,
+                system_id: 0,
+                component_id: 0,
+            },
+            &MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+                custom_mode: 0,
+                mavtype: MavType::MAV_TYPE_QUADROTOR,
+                autopilot: MavAutopilot::MAV_AUTOPILOT_PX4,
+                base_mode: MavModeFlag::empty(),
+                system_status: MavState::MAV_STATE_STANDBY,
+                mavlink_version: 0x3,
+            }),
+        );
+        let first_entry_len = 8 + probe.raw_bytes().len();
+        let second_entry_start = header_len + first_entry_len;
+        packed_data.splice(second_entry_start..second_entry_start, [0xAA, 0xAA, 0xAA]);
+
+        temp_file
+            .write(&packed_data)
+            .expect("Failed to write modified test file");
+
+        let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            Some(ParseMode::Recover),
+        )
+        .expect("Failed to create parser");
+
+        let first_entry = parser.next();
+        assert!(
+            first_entry.is_ok(),
+            "First entry failed: {:?}",
+            first_entry.err()
+        );
+        assert_eq!(first_entry.unwrap().timestamp, Some(0));
+        assert_eq!(
+            parser.recovery_stats().skipped_bytes,
+            0,
+            "No recovery should have happened yet"
+        );
+
+        let second_entry = parser.next();
+        assert!(
+            second_entry.is_ok(),
+            "Recover mode should resync past the injected garbage instead of giving up: {:?}",
+            second_entry.err()
+        );
+        assert_eq!(second_entry.unwrap().timestamp, Some(1));
+        assert_eq!(
+            parser.recovery_stats().skipped_bytes,
+            3,
+            "Should have skipped exactly the 3 injected garbage bytes"
+        );
+        assert_eq!(parser.recovery_stats().resyncs, 1);
+
+        // Everything after the resync point should parse normally.
+        for i in 2..60 {
+            let entry = parser.next();
+            assert!(entry.is_ok(), "Iteration: {i} {:?}", entry.err());
+            assert_eq!(entry.unwrap().timestamp, Some(i as u64));
+        }
+        assert_eq!(
+            parser.recovery_stats().resyncs,
+            1,
+            "No further recovery should have been needed"
+        );
+        temp_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_mav_log_parser_seek_to_timestamp_and_entry_use_sidecar_index() {
+        // Same mavlink-only, timestamped layout as the recover-mode test: 60 entries,
+        // timestamped 0..59 in file order.
+        let temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+        let mut packed_data: Vec<u8> = vec![
+            // file header
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, // uuid
+            16, 0, 0, 0, 0, 0, 0, 17, // timestamp_us
+            b'a', b'p', b'p', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, // src_application_id
+            1, 0, 0, 0, // format_version
+            1, 0, // format_flags
+            // message_definition
+            2, 0, 0, 0, // version_major
+            1, 0, 0, 0, // version_minor
+            b't', b'e', b's', b't', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, // dialect
+            0, 0, // payload_type
+            0, 0, 0, 0, // size
+        ];
+        populate_data(true, true, &mut packed_data);
+        std::fs::write(temp_file.path(), &packed_data).expect("Failed to write test file");
+
+        let idx_path = {
+            let mut p = temp_file.path().to_path_buf();
+            let mut file_name = p.file_name().unwrap().to_os_string();
+            file_name.push(".idx");
+            p.set_file_name(file_name);
+            p
+        };
+        assert!(!idx_path.exists(), "sidecar shouldn't exist before the first index build");
+
+        let mut parser = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            None,
+        )
+        .expect("Failed to create parser");
+
+        parser.seek_to_entry(10).expect("Failed to seek to entry 10");
+        assert_eq!(parser.next().expect("Failed to read entry 10").timestamp, Some(10));
+        assert!(idx_path.exists(), "build_index should have persisted a sidecar");
+
+        parser
+            .seek_to_timestamp(25)
+            .expect("Failed to seek to timestamp 25");
+        assert_eq!(
+            parser.next().expect("Failed to read entry timestamped 25").timestamp,
+            Some(25)
+        );
+
+        // A fresh parser over the same file should pick up the sidecar the first one
+        // wrote, rather than rescanning, and still seek correctly.
+        let mut reopened = MavLogParser::<mavlink::ardupilotmega::MavMessage>::new(
+            temp_file.path().to_str().unwrap(),
+            None,
+        )
+        .expect("Failed to reopen parser");
+        reopened
+            .seek_to_entry(0)
+            .expect("Failed to seek to the first entry");
+        assert_eq!(reopened.next().expect("Failed to read entry 0").timestamp, Some(0));
+
+        temp_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_mav_log_writer_round_trips_through_mav_log_parser() {
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+
+        let header = FileHeader {
+            uuid: Uuid::from_bytes([0; 16]),
+            timestamp_us: 0,
+            src_application_id: "test".to_string(),
+            format_version: 1,
+            format_flags: FormatFlags::default(),
+            message_definition: MavlinkMessageDefinition {
+                version_major: 2,
+                version_minor: 0,
+                dialect: "common".to_string(),
+                payload_type: MavlinkDefinitionPayloadType::None,
+                size: 0,
+                payload: None,
+                resolved_dialect: None,
+            },
+        };
+
+        let mut writer: MavLogWriter<MavMessage, _> =
+            MavLogWriter::new(temp_file.reopen().unwrap(), header).expect("Failed to create writer");
+
+        let mav_header = MavHeader {
+            sequence: 0,
+            system_id: 1,
+            component_id: 2,
+        };
+        let heartbeat = HEARTBEAT_DATA {
+            custom_mode: 0,
+            mavtype: MavType::MAV_TYPE_QUADROTOR,
+            autopilot: MavAutopilot::MAV_AUTOPILOT_PX4,
+            base_mode: MavModeFlag::empty(),
+            system_status: MavState::MAV_STATE_STANDBY,
+            mavlink_version: 0x3,
+        };
+
+        writer
+            .write_mavlink(mav_header, &MavMessage::HEARTBEAT(heartbeat.clone()), 0)
+            .expect("Failed to write mavlink entry");
+        writer
+            .write_text("hello", 0)
+            .expect("Failed to write text entry");
+        writer
+            .write_raw(&[1, 2, 3], 0)
+            .expect("Failed to write raw entry");
+        drop(writer);
+
+        let mut parser = MavLogParser::<MavMessage>::new(temp_file.path().to_str().unwrap(), None)
+            .expect("Failed to create parser");
+
+        let mavlink_entry = parser.next().expect("Failed to parse mavlink entry");
+        assert!(mavlink_entry.mav_header.is_some());
+        match mavlink_entry.mav_message {
+            Some(MavMessage::HEARTBEAT(data)) => assert_eq!(data.custom_mode, 0),
+            other => panic!("expected a HEARTBEAT message, got {other:?}"),
+        }
+
+        let text_entry = parser.next().expect("Failed to parse text entry");
+        assert_eq!(text_entry.text.unwrap(), "hello");
+
+        let raw_entry = parser.next().expect("Failed to parse raw entry");
+        assert_eq!(raw_entry.raw.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_mav_log_writer_write_entry_round_trips_each_entry_kind() {
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+
+        let header = FileHeader {
+            uuid: Uuid::from_bytes([0; 16]),
+            timestamp_us: 0,
+            src_application_id: "test".to_string(),
+            format_version: 1,
+            format_flags: FormatFlags::default(),
+            message_definition: MavlinkMessageDefinition {
+                version_major: 2,
+                version_minor: 0,
+                dialect: "common".to_string(),
+                payload_type: MavlinkDefinitionPayloadType::None,
+                size: 0,
+                payload: None,
+                resolved_dialect: None,
+            },
+        };
+
+        let mut writer: MavLogWriter<MavMessage, _> =
+            MavLogWriter::new(temp_file.reopen().unwrap(), header).expect("Failed to create writer");
+
+        let mavlink_entry = LogEntry {
+            timestamp: None,
+            mav_header: Some(MavHeader {
+                sequence: 0,
+                system_id: 1,
+                component_id: 2,
+            }),
+            mav_message: Some(MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+                custom_mode: 0,
+                mavtype: MavType::MAV_TYPE_QUADROTOR,
+                autopilot: MavAutopilot::MAV_AUTOPILOT_PX4,
+                base_mode: MavModeFlag::empty(),
+                system_status: MavState::MAV_STATE_STANDBY,
+                mavlink_version: 0x3,
+            })),
+            mav_version: None,
+            text: None,
+            raw: None,
+        };
+        let text_entry = LogEntry {
+            text: Some("hello".to_string()),
+            ..LogEntry::default()
+        };
+        let raw_entry = LogEntry {
+            raw: Some(vec![1, 2, 3]),
+            ..LogEntry::default()
+        };
+        let empty_entry: LogEntry<MavMessage> = LogEntry::default();
+
+        writer
+            .write_entry(&mavlink_entry)
+            .expect("Failed to write mavlink entry");
+        writer
+            .write_entry(&text_entry)
+            .expect("Failed to write text entry");
+        writer
+            .write_entry(&raw_entry)
+            .expect("Failed to write raw entry");
+        assert!(writer.write_entry(&empty_entry).is_err());
+        drop(writer);
+
+        let mut parser = MavLogParser::<MavMessage>::new(temp_file.path().to_str().unwrap(), None)
+            .expect("Failed to create parser");
+
+        let parsed_mavlink = parser.next().expect("Failed to parse mavlink entry");
+        match parsed_mavlink.mav_message {
+            Some(MavMessage::HEARTBEAT(data)) => assert_eq!(data.custom_mode, 0),
+            other => panic!("expected a HEARTBEAT message, got {other:?}"),
+        }
+
+        let parsed_text = parser.next().expect("Failed to parse text entry");
+        assert_eq!(parsed_text.text.unwrap(), "hello");
+
+        let parsed_raw = parser.next().expect("Failed to parse raw entry");
+        assert_eq!(parsed_raw.raw.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_filter_message_ids_skips_non_matching_entries() {
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+
+        let header = FileHeader {
+            uuid: Uuid::from_bytes([0; 16]),
+            timestamp_us: 0,
+            src_application_id: "test".to_string(),
+            format_version: 1,
+            format_flags: FormatFlags::default(),
+            message_definition: MavlinkMessageDefinition {
+                version_major: 2,
+                version_minor: 0,
+                dialect: "common".to_string(),
+                payload_type: MavlinkDefinitionPayloadType::None,
+                size: 0,
+                payload: None,
+                resolved_dialect: None,
+            },
+        };
+
+        let mut writer: MavLogWriter<MavMessage, _> =
+            MavLogWriter::new(temp_file.reopen().unwrap(), header).expect("Failed to create writer");
+
+        let mav_header = MavHeader {
+            sequence: 0,
+            system_id: 1,
+            component_id: 2,
+        };
+        let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            custom_mode: 0,
+            mavtype: MavType::MAV_TYPE_QUADROTOR,
+            autopilot: MavAutopilot::MAV_AUTOPILOT_PX4,
+            base_mode: MavModeFlag::empty(),
+            system_status: MavState::MAV_STATE_STANDBY,
+            mavlink_version: 0x3,
+        });
+        let attitude = MavMessage::ATTITUDE(ATTITUDE_DATA {
+            time_boot_ms: 0,
+            roll: 0.0,
+            pitch: 0.0,
+            yaw: 0.0,
+            rollspeed: 0.0,
+            pitchspeed: 0.0,
+            yawspeed: 0.0,
+        });
+        let heartbeat_id = heartbeat.message_id();
+
+        writer
+            .write_mavlink(mav_header, &attitude, 0)
+            .expect("Failed to write attitude entry");
+        writer
+            .write_mavlink(mav_header, &heartbeat, 0)
+            .expect("Failed to write heartbeat entry");
+        writer
+            .write_mavlink(mav_header, &attitude, 0)
+            .expect("Failed to write attitude entry");
+        drop(writer);
+
+        let parser = MavLogParser::<MavMessage>::new(temp_file.path().to_str().unwrap(), None)
+            .expect("Failed to create parser");
+        let mut filtered = parser.filter_message_ids(&[heartbeat_id]);
+
+        let entry = filtered.next().expect("Failed to parse filtered entry");
+        match entry.mav_message {
+            Some(MavMessage::HEARTBEAT(_)) => {}
+            other => panic!("expected a HEARTBEAT message, got {other:?}"),
+        }
+        assert!(
+            filtered.next().is_err(),
+            "expected no further entries to match the id filter"
+        );
+    }
+
+    #[test]
+    fn test_filter_messages_combines_message_id_and_system_id_predicates() {
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+
+        let header = FileHeader {
+            uuid: Uuid::from_bytes([0; 16]),
+            timestamp_us: 0,
+            src_application_id: "test".to_string(),
+            format_version: 1,
+            format_flags: FormatFlags::default(),
+            message_definition: MavlinkMessageDefinition {
+                version_major: 2,
+                version_minor: 0,
+                dialect: "common".to_string(),
+                payload_type: MavlinkDefinitionPayloadType::None,
+                size: 0,
+                payload: None,
+                resolved_dialect: None,
+            },
+        };
+
+        let mut writer: MavLogWriter<MavMessage, _> =
+            MavLogWriter::new(temp_file.reopen().unwrap(), header).expect("Failed to create writer");
+
+        let attitude = MavMessage::ATTITUDE(ATTITUDE_DATA {
+            time_boot_ms: 0,
+            roll: 0.0,
+            pitch: 0.0,
+            yaw: 0.0,
+            rollspeed: 0.0,
+            pitchspeed: 0.0,
+            yawspeed: 0.0,
+        });
+        let attitude_id = attitude.message_id();
+
+        let system_1_header = MavHeader {
+            sequence: 0,
+            system_id: 1,
+            component_id: 2,
+        };
+        let system_2_header = MavHeader {
+            sequence: 0,
+            system_id: 2,
+            component_id: 2,
+        };
+
+        // An ATTITUDE from system 1, an ATTITUDE from system 2, and a non-matching
+        // text entry, so the query has to reject a wrong system id and a wrong kind.
+        writer
+            .write_mavlink(system_1_header, &attitude, 0)
+            .expect("Failed to write attitude entry");
+        writer
+            .write_mavlink(system_2_header, &attitude, 0)
+            .expect("Failed to write attitude entry");
+        writer.write_text("hello", 0).expect("Failed to write text entry");
+        drop(writer);
+
+        let parser = MavLogParser::<MavMessage>::new(temp_file.path().to_str().unwrap(), None)
+            .expect("Failed to create parser");
+        let mut filtered = parser.filter_messages(FilterSpec {
+            message_ids: Some(vec![attitude_id]),
+            system_ids: Some(vec![1]),
+            kinds: Some(vec![EntryKind::Mavlink]),
+            ..Default::default()
+        });
+
+        let entry = filtered.next().expect("Failed to parse filtered entry");
+        assert_eq!(entry.mav_header.unwrap().system_id, 1);
+        match entry.mav_message {
+            Some(MavMessage::ATTITUDE(_)) => {}
+            other => panic!("expected an ATTITUDE message, got {other:?}"),
+        }
+        assert!(
+            filtered.next().is_err(),
+            "expected no further entries to match the query"
+        );
+    }
+
+    #[test]
+    fn test_compute_crc_extra_matches_known_heartbeat_value() {
+        let xml = r#"<mavlink>
+  <messages>
+    <message id="0" name="HEARTBEAT">
+      <field type="uint8_t" name="type">Type of the system</field>
+      <field type="uint8_t" name="autopilot">Autopilot type</field>
+      <field type="uint8_t" name="base_mode">System mode bitmap</field>
+      <field type="uint32_t" name="custom_mode">A bitfield for use for autopilot-specific flags</field>
+      <field type="uint8_t" name="system_status">System status flag</field>
+      <field type="uint8_t_mavlink_version" name="mavlink_version">MAVLink version</field>
+    </message>
+  </messages>
+</mavlink>"#;
+        let dialect = mavlink_log_parser::mav_parser::dialect::parse_xml_dialect(xml)
+            .expect("Failed to parse dialect XML");
+        let heartbeat = dialect.messages.get(&0).expect("HEARTBEAT should be defined");
+        assert_eq!(heartbeat.crc_extra, Some(50));
+    }
+
+    #[test]
+    fn test_dialect_decode_frame_decodes_a_v2_frame_against_a_dynamic_dialect() {
+        let xml = r#"<mavlink>
+  <messages>
+    <message id="150" name="FOO">
+      <field type="uint8_t" name="a">an example field</field>
+      <field type="uint16_t" name="b">another example field</field>
+    </message>
+  </messages>
+</mavlink>"#;
+        let dialect = mavlink_log_parser::mav_parser::dialect::parse_xml_dialect(xml)
+            .expect("Failed to parse dialect XML");
+
+        // Wire order puts `b` (uint16_t, 2 bytes) ahead of `a` (uint8_t, 1 byte).
+        let mut raw = vec![
+            mavlink::MAV_STX_V2,
+            3, // payload length
+            0, // incompat flags
+            0, // compat flags
+            0, // sequence
+            1, // system id
+            1, // component id
+            150, 0, 0, // message id (little-endian, 3 bytes)
+        ];
+        raw.extend_from_slice(&42u16.to_le_bytes()); // b
+        raw.push(7); // a
+        raw.extend_from_slice(&[0, 0]); // crc, unused by decode_frame
+
+        let message = dialect.decode_frame(&raw).expect("Failed to decode frame");
+        assert_eq!(message.id, 150);
+        assert_eq!(message.name, "FOO");
+        assert_eq!(
+            message.fields,
+            vec![
+                (
+                    "b".to_string(),
+                    mavlink_log_parser::mav_parser::dynamic::Value::UInt16(42)
+                ),
+                (
+                    "a".to_string(),
+                    mavlink_log_parser::mav_parser::dynamic::Value::UInt8(7)
+                ),
+            ]
+        );
+    }
+
     fn populate_data(mavlink_only: bool, timestamp: bool, data: &mut Vec<u8>) {
         let mut msg = MAVLinkV2MessageRaw::new();
         let mut header = MavHeader {
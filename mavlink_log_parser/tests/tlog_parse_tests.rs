@@ -5,6 +5,8 @@
 mod tlog_parse_tests {
     use mavlink::ardupilotmega::MavMessage;
     use mavlink::error::MessageReadError;
+    use mavlink::Message;
+    use mavlink_log_parser::log_index::LogIndex;
     use mavlink_log_parser::tlog_parser::TlogParser;
     use mavlink_log_parser::{LogEntry, MavParser};
 
@@ -31,4 +33,49 @@ mod tlog_parse_tests {
         }
         assert_eq!(count, 1426);
     }
+
+    /// Verifies that `LogIndex` recovers the same message count `test_tlog_parse`
+    /// does via forward iteration, and that `seek_to_time`/`iter_message_id` land
+    /// on a record whose fields agree with the indexed metadata.
+    #[test]
+    fn test_log_index_build_and_seek() {
+        let mut index = LogIndex::<MavMessage>::build("tests/data/tlog_data_0.tlog")
+            .expect("Failed to build log index");
+        assert_eq!(index.entries().len(), 1426);
+
+        let last = *index.entries().last().expect("index should not be empty");
+        let target_timestamp = last.timestamp.expect("last entry should have a timestamp");
+
+        index
+            .seek_to_time(target_timestamp)
+            .expect("Failed to seek to the last entry's timestamp");
+        let entry = index.next().expect("Failed to read the sought-to entry");
+        assert_eq!(entry.timestamp, Some(target_timestamp));
+        assert_eq!(
+            entry.mav_message.map(|m| m.message_id()),
+            Some(last.message_id)
+        );
+
+        let offsets: Vec<u64> = index.iter_message_id(last.message_id).collect();
+        assert!(offsets.contains(&last.offset));
+    }
+
+    /// Verifies that `TlogParser::with_version` decodes every record under the
+    /// forced version and surfaces it on `LogEntry::mav_version`, matching what
+    /// the auto-detecting `new` reports for the first record.
+    #[test]
+    fn test_tlog_parse_with_forced_version_matches_auto_detected_version() {
+        let mut auto = TlogParser::<MavMessage>::new("tests/data/tlog_data_0.tlog");
+        let first_entry = auto.next().expect("Failed to parse the first entry");
+        let detected_version = first_entry
+            .mav_version
+            .expect("auto-detected entries should report a mav_version");
+
+        let mut forced = TlogParser::<MavMessage>::with_version(
+            "tests/data/tlog_data_0.tlog",
+            detected_version,
+        );
+        let forced_entry = forced.next().expect("Failed to parse under the forced version");
+        assert_eq!(forced_entry.mav_version, Some(detected_version));
+    }
 }
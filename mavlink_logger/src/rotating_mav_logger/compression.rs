@@ -0,0 +1,36 @@
+//! Optional per-record payload compression for `RotatingFileMavLogger`.
+
+use std::io;
+
+/// A compression algorithm that can be applied to individual record payloads.
+///
+/// The writer only stores a payload compressed if doing so actually shrinks it;
+/// otherwise it falls back to storing the payload raw and clears the per-record
+/// compressed flag, so enabling compression never inflates small records.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Compression {
+    /// LZ4 block compression. Fast, with a modest compression ratio.
+    Lz4 = 0,
+    /// Zstandard compression. Slower than LZ4, but compresses better.
+    Zstd = 1,
+}
+
+impl Compression {
+    /// Compresses `data`, returning the compressed bytes.
+    pub(super) fn compress(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Compression::Lz4 => lz4_flex::compress_prepend_size(data),
+            Compression::Zstd => zstd::encode_all(data, 0).expect("zstd compression failed"),
+        }
+    }
+
+    /// Decompresses `data` that was produced by `compress`.
+    pub(super) fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::Lz4 => lz4_flex::decompress_size_prepended(data)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+            Compression::Zstd => zstd::decode_all(data)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+        }
+    }
+}
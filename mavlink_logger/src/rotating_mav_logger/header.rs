@@ -1,27 +1,37 @@
+use std::convert::TryFrom;
+use std::convert::TryInto;
 use std::time::SystemTime;
 
 use uuid::Uuid;
 
+use super::compression::Compression;
+
 /// Struct representing format flags for the log file.
 ///
 /// `FormatFlags` contains options that modify the format of the log file.
 /// - `mavlink_only`: If set, only MAVLink messages are logged allowing for a more compact log file.
 /// - `not_timestamped`: If set, timestamps per entry are not included in the log file.
+/// - `compression`: If set, record payloads are compressed with the given algorithm.
 pub struct FormatFlags {
     /// If set, only MAVLink messages are logged allowing for a more compact log file.
     pub mavlink_only: bool,
     /// If set, timestamps per entry are not included in the log file.
     pub not_timestamped: bool,
+    /// If set, record payloads are compressed with the given algorithm. A record is only
+    /// stored compressed if doing so actually shrinks it; see `Compression`.
+    pub compression: Option<Compression>,
 }
 
 impl Default for FormatFlags {
     /// Provides default values for `FormatFlags`.
     ///
-    /// By default, both `mavlink_only` and `not_timestamped` are set to `false`.
+    /// By default, `mavlink_only` and `not_timestamped` are set to `false`, and
+    /// `compression` is disabled.
     fn default() -> Self {
         FormatFlags {
             mavlink_only: false,
             not_timestamped: false,
+            compression: None,
         }
     }
 }
@@ -29,14 +39,45 @@ impl Default for FormatFlags {
 impl FormatFlags {
     /// Packs the `FormatFlags` into a 2-byte array.
     ///
-    /// This method converts the `FormatFlags` into a 2-byte array where each flag is represented by a bit.
+    /// This method converts the `FormatFlags` into a 2-byte array where `mavlink_only` and
+    /// `not_timestamped` are each represented by a bit, and `compression` is packed into the
+    /// next two bits (`00` disabled, `01` LZ4, `10` Zstd).
     ///
     /// # Returns
     /// A `[u8; 2]` array containing the packed representation of the `FormatFlags`.
     pub fn pack(&self) -> [u8; 2] {
-        let flags: u16 = (self.mavlink_only as u16) | ((self.not_timestamped as u16) << 1);
+        let compression_bits: u16 = match self.compression {
+            None => 0b00,
+            Some(Compression::Lz4) => 0b01,
+            Some(Compression::Zstd) => 0b10,
+        };
+        let flags: u16 = (self.mavlink_only as u16)
+            | ((self.not_timestamped as u16) << 1)
+            | (compression_bits << 2);
         flags.to_le_bytes()
     }
+
+    /// Unpacks `FormatFlags` from its packed little-endian representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed_data` - The 2-byte little-endian representation produced by `pack`.
+    ///
+    /// # Returns
+    /// A `FormatFlags` instance with the bits decoded.
+    pub fn unpack(packed_data: [u8; 2]) -> Self {
+        let flags: u16 = u16::from_le_bytes(packed_data);
+        let compression = match (flags >> 2) & 0b11 {
+            0b01 => Some(Compression::Lz4),
+            0b10 => Some(Compression::Zstd),
+            _ => None,
+        };
+        FormatFlags {
+            mavlink_only: flags & 0x01 != 0,
+            not_timestamped: flags & 0x02 != 0,
+            compression,
+        }
+    }
 }
 
 /// Enum representing the payload type for MAVLink message definitions.
@@ -55,6 +96,20 @@ pub enum MavlinkDefinitionPayloadType {
     Utf8Xml = 2,
 }
 
+impl TryFrom<u16> for MavlinkDefinitionPayloadType {
+    type Error = ();
+
+    /// Converts the packed `u16` payload type back into a `MavlinkDefinitionPayloadType`.
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MavlinkDefinitionPayloadType::None),
+            1 => Ok(MavlinkDefinitionPayloadType::Utf8SpaceDelimitedUrlsForXMLFiles),
+            2 => Ok(MavlinkDefinitionPayloadType::Utf8Xml),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Struct representing a MAVLink message definition.
 ///
 /// `MavlinkMessageDefinition` contains information about the MAVLink protocol version, dialect, payload type, and the actual payload.
@@ -105,6 +160,47 @@ impl MavlinkMessageDefinition {
         }
         packed
     }
+
+    /// Unpacks the fixed 46-byte portion of a `MavlinkMessageDefinition` from a log file.
+    ///
+    /// This reverses `pack`'s layout (major/minor version, null-padded 32-byte dialect,
+    /// payload type, and payload size). The variable-length payload, if any, must be
+    /// supplied separately via `unpack_payload` once `size` bytes have been read.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed_data` - The 46-byte fixed-size header produced by `pack`.
+    ///
+    /// # Returns
+    /// A `MavlinkMessageDefinition` with `payload` left empty.
+    pub fn unpack(packed_data: &[u8; 46]) -> Self {
+        // Stop at the first null byte when unpacking the dialect string.
+        let end_dialect_ind: usize = match packed_data[8..40].iter().position(|&x| x == 0) {
+            Some(index) => index + 8,
+            None => 40,
+        };
+        MavlinkMessageDefinition {
+            version_major: u32::from_le_bytes(packed_data[0..4].try_into().unwrap()),
+            version_minor: u32::from_le_bytes(packed_data[4..8].try_into().unwrap()),
+            dialect: String::from_utf8_lossy(&packed_data[8..end_dialect_ind]).into_owned(),
+            payload_type: u16::from_le_bytes(packed_data[40..42].try_into().unwrap())
+                .try_into()
+                .unwrap_or(MavlinkDefinitionPayloadType::None),
+            size: u32::from_le_bytes(packed_data[42..46].try_into().unwrap()),
+            payload: Vec::new(),
+        }
+    }
+
+    /// Fills in the variable-length payload once it has been read from the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed_data` - The raw payload bytes following the fixed 46-byte header.
+    pub fn unpack_payload(&mut self, packed_data: &[u8]) {
+        if self.payload_type != MavlinkDefinitionPayloadType::None {
+            self.payload = packed_data.to_vec();
+        }
+    }
 }
 
 impl Default for MavlinkMessageDefinition {
@@ -214,6 +310,36 @@ impl FileHeader {
         packed.extend_from_slice(&self.message_definition.pack());
         packed
     }
+
+    /// Unpacks the fixed `MIN_SIZE`-byte portion of a `FileHeader` from a log file.
+    ///
+    /// This reverses `pack`'s layout. The message definition's variable-length payload,
+    /// if any, must be read separately and supplied via
+    /// `MavlinkMessageDefinition::unpack_payload`.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed_data` - The `FileHeader::MIN_SIZE`-byte fixed-size header produced by `pack`.
+    ///
+    /// # Returns
+    /// A `FileHeader` with `message_definition.payload` left empty.
+    pub fn unpack(packed_data: &[u8; FileHeader::MIN_SIZE]) -> Self {
+        let id_end: usize = match packed_data[24..56].iter().position(|&x| x == 0) {
+            Some(index) => index + 24,
+            None => 56,
+        };
+
+        FileHeader {
+            uuid: Uuid::from_bytes(packed_data[0..16].try_into().unwrap()),
+            timestamp_us: u64::from_le_bytes(packed_data[16..24].try_into().unwrap()),
+            src_application_id: String::from_utf8_lossy(&packed_data[24..id_end]).into_owned(),
+            format_version: u32::from_le_bytes(packed_data[56..60].try_into().unwrap()),
+            format_flags: FormatFlags::unpack(packed_data[60..62].try_into().unwrap()),
+            message_definition: MavlinkMessageDefinition::unpack(
+                packed_data[62..108].try_into().unwrap(),
+            ),
+        }
+    }
 }
 
 impl Default for FileHeader {
@@ -254,26 +380,44 @@ mod tests {
         let flags = FormatFlags {
             mavlink_only: false,
             not_timestamped: false,
+            compression: None,
         };
         assert_eq!(flags.pack(), [0, 0]);
 
         let flags = FormatFlags {
             mavlink_only: true,
             not_timestamped: false,
+            compression: None,
         };
         assert_eq!(flags.pack(), [1, 0]);
 
         let flags = FormatFlags {
             mavlink_only: false,
             not_timestamped: true,
+            compression: None,
         };
         assert_eq!(flags.pack(), [2, 0]);
 
         let flags = FormatFlags {
             mavlink_only: true,
             not_timestamped: true,
+            compression: None,
         };
         assert_eq!(flags.pack(), [3, 0]);
+
+        let flags = FormatFlags {
+            mavlink_only: false,
+            not_timestamped: false,
+            compression: Some(Compression::Lz4),
+        };
+        assert_eq!(flags.pack(), [0b0100, 0]);
+
+        let flags = FormatFlags {
+            mavlink_only: true,
+            not_timestamped: true,
+            compression: Some(Compression::Zstd),
+        };
+        assert_eq!(flags.pack(), [0b1011, 0]);
     }
 
     #[test]
@@ -340,6 +484,7 @@ mod tests {
         let format_flags = FormatFlags {
             mavlink_only: true,
             not_timestamped: false,
+            compression: None,
         };
         let message_definition = MavlinkMessageDefinition {
             version_major: 2,
@@ -361,4 +506,71 @@ mod tests {
         assert_eq!(&packed[60..62], &[1, 0]); // format flags
         assert_eq!(&packed[62..113], &header.message_definition.pack()[..]);
     }
+
+    #[test]
+    /// Tests that `FormatFlags::unpack` reverses `pack` for every flag combination.
+    fn test_format_flags_unpack_round_trip() {
+        for mavlink_only in [false, true] {
+            for not_timestamped in [false, true] {
+                for compression in [None, Some(Compression::Lz4), Some(Compression::Zstd)] {
+                    let flags = FormatFlags {
+                        mavlink_only,
+                        not_timestamped,
+                        compression,
+                    };
+                    let unpacked = FormatFlags::unpack(flags.pack());
+                    assert_eq!(unpacked.mavlink_only, mavlink_only);
+                    assert_eq!(unpacked.not_timestamped, not_timestamped);
+                    assert_eq!(unpacked.compression, compression);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Tests that `MavlinkMessageDefinition::unpack`/`unpack_payload` reverse `pack`.
+    fn test_mavlink_message_definition_unpack_round_trip() {
+        let definition = MavlinkMessageDefinition {
+            version_major: 0x01020304,
+            version_minor: 0x04050607,
+            dialect: String::from(MavlinkMessageDefinition::DEFAULT_DIALECT),
+            payload_type: MavlinkDefinitionPayloadType::Utf8Xml,
+            size: 5,
+            payload: b"hello".to_vec(),
+        };
+        let packed = definition.pack();
+        let fixed: &[u8; 46] = packed[0..46].try_into().unwrap();
+        let mut unpacked = MavlinkMessageDefinition::unpack(fixed);
+        unpacked.unpack_payload(&packed[46..]);
+
+        assert_eq!(unpacked.version_major, definition.version_major);
+        assert_eq!(unpacked.version_minor, definition.version_minor);
+        assert_eq!(unpacked.dialect, definition.dialect);
+        assert_eq!(unpacked.payload_type, definition.payload_type);
+        assert_eq!(unpacked.size, definition.size);
+        assert_eq!(unpacked.payload, definition.payload);
+    }
+
+    #[test]
+    /// Tests that `FileHeader::unpack` reverses `pack` for the fixed-size portion.
+    fn test_file_header_unpack_round_trip() {
+        let format_flags = FormatFlags {
+            mavlink_only: true,
+            not_timestamped: false,
+            compression: Some(Compression::Zstd),
+        };
+        let header = FileHeader::new(format_flags, MavlinkMessageDefinition::default());
+        let packed = header.pack();
+        let fixed: &[u8; FileHeader::MIN_SIZE] =
+            packed[0..FileHeader::MIN_SIZE].try_into().unwrap();
+        let unpacked = FileHeader::unpack(fixed);
+
+        assert_eq!(unpacked.uuid, header.uuid);
+        assert_eq!(unpacked.timestamp_us, header.timestamp_us);
+        assert_eq!(unpacked.src_application_id, header.src_application_id);
+        assert_eq!(unpacked.format_version, header.format_version);
+        assert!(unpacked.format_flags.mavlink_only);
+        assert!(!unpacked.format_flags.not_timestamped);
+        assert_eq!(unpacked.format_flags.compression, Some(Compression::Zstd));
+    }
 }
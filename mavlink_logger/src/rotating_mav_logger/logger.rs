@@ -1,34 +1,179 @@
 /// This module defines a rotating file logger for MAVLink messages.
 /// It supports logging raw data, text, and MAVLink messages with optional
 /// format flags and message definitions.
+use std::convert::TryFrom;
 use std::option::Option;
 use std::option::Option::Some;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use mavlink::{MAVLinkV1MessageRaw, MAVLinkV2MessageRaw};
 use mavlink::{MavFrame, Message};
+#[cfg(feature = "std")]
 use rotating_file_handler::RotatingFileHandler;
 
 use super::header::{FileHeader, FormatFlags, MavlinkMessageDefinition};
 use crate::MavLogger;
 
+/// A minimal, backend-agnostic byte sink that `RotatingFileMavLogger`'s record
+/// framing writes through, modeled on `embedded_io::Write`.
+///
+/// This keeps the framing logic in `write()`/`write_text()`/`write_raw()` reusable
+/// against destinations other than a desktop rotating file: a FAT-formatted SD card
+/// on a flight controller, a WASI file descriptor, or any other sink that can accept
+/// a slice of bytes.
+pub trait MavLogSink {
+    /// Writes `buf` to the sink in full.
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()>;
+
+    /// Forces the sink to roll over to a fresh destination, writing `preamble`
+    /// (the new `FileHeader`) to the start of it.
+    ///
+    /// Sinks with no notion of rotation (e.g. a plain in-memory buffer) can leave
+    /// this at its default no-op; a `rotation_policy` boundary then writes no new
+    /// header, but still flushes the queue and restarts per-record timestamps
+    /// from zero.
+    fn rollover(&mut self, preamble: Option<&[u8]>) -> std::io::Result<()> {
+        let _ = preamble;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl MavLogSink for RotatingFileHandler {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.emit(buf)
+    }
+
+    fn rollover(&mut self, preamble: Option<&[u8]>) -> std::io::Result<()> {
+        RotatingFileHandler::rollover(self, preamble)
+    }
+}
+
+/// Adapts any `std::io::Write` sink (a plain file, a WASI file descriptor, an
+/// in-memory buffer, ...) into a `MavLogSink` with no rotation support.
+#[cfg(feature = "std")]
+pub struct IoWriteSink<W: std::io::Write>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> MavLogSink for IoWriteSink<W> {
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        std::io::Write::write_all(&mut self.0, buf)
+    }
+}
+
 /// Enum representing the type of log entry.
-#[derive(PartialEq, Debug)]
-enum EntryType {
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(super) enum EntryType {
     Raw = 0,
     Mavlink = 1,
     Text = 2,
 }
 
+impl TryFrom<u8> for EntryType {
+    type Error = ();
+
+    /// Converts the on-disk type byte back into an `EntryType`.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(EntryType::Raw),
+            1 => Ok(EntryType::Mavlink),
+            2 => Ok(EntryType::Text),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A time-based policy that forces a log rotation independently of file size.
+///
+/// This lets callers roll logs at wall-clock boundaries, which matters for
+/// flight logs where a vehicle idles for hours producing small files that
+/// should still roll at day boundaries.
+#[derive(Clone, Copy)]
+pub enum RotationPolicy {
+    /// Rotate every time this much wall-clock time has elapsed since the last rotation.
+    Interval(Duration),
+    /// Rotate at the top of every hour (UTC).
+    Hourly,
+    /// Rotate at midnight (UTC) every day.
+    Daily,
+}
+
+impl RotationPolicy {
+    /// Computes the next rotation instant after `from`, aligned to this policy's boundary.
+    fn next_rotation_after(&self, from: SystemTime) -> SystemTime {
+        match self {
+            RotationPolicy::Interval(duration) => from + *duration,
+            RotationPolicy::Hourly => Self::next_boundary_after(from, Duration::from_secs(3600)),
+            RotationPolicy::Daily => Self::next_boundary_after(from, Duration::from_secs(86400)),
+        }
+    }
+
+    /// Returns the next instant after `from` that falls on a `period`-aligned boundary
+    /// relative to the Unix epoch.
+    fn next_boundary_after(from: SystemTime, period: Duration) -> SystemTime {
+        let since_epoch = from.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+        let period_secs = period.as_secs();
+        let elapsed_periods = since_epoch.as_secs() / period_secs;
+        UNIX_EPOCH + Duration::from_secs((elapsed_periods + 1) * period_secs)
+    }
+}
+
+/// A policy controlling when buffered writes are flushed to the underlying file.
+///
+/// Buffering trades durability for throughput: records accumulate in memory and are
+/// only handed to the file handler (and thus actually written) once the policy's
+/// condition is met, or once the buffer exceeds `RotatingFileMavLogger`'s configured
+/// size threshold, whichever comes first.
+#[derive(Clone, Copy)]
+pub enum SyncPolicy {
+    /// Flush after every record, matching the logger's original behavior.
+    Immediate,
+    /// Flush once this many records have been buffered.
+    EveryN(usize),
+    /// Flush once this much wall-clock time has elapsed since the last flush.
+    Interval(Duration),
+}
+
+/// Default size, in bytes, at which the write buffer is flushed regardless of
+/// `SyncPolicy`, used when `RotatingFileMavLogger::new` is not given an explicit
+/// `max_buffered_bytes`.
+pub const DEFAULT_MAX_BUFFERED_BYTES: usize = 64 * 1024;
+
+/// The policy applied to the record queue once it reaches `max_queued_records`,
+/// protecting the caller from blocking (or exhausting memory) under burst load.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DropPolicy {
+    /// Discard the record that was about to be queued, keeping the older ones.
+    DropNewest,
+    /// Discard the oldest queued record to make room for the new one.
+    DropOldest,
+}
+
 /// Struct representing a rotating file logger for MAVLink messages.
-pub struct RotatingFileMavLogger {
+///
+/// Generic over the byte sink `W`, so the same record-framing logic can target a
+/// desktop rotating file (`W = RotatingFileHandler`, the default) or any other
+/// destination implementing `MavLogSink`.
+pub struct RotatingFileMavLogger<W: MavLogSink = RotatingFileHandler> {
     header: FileHeader,
     time: SystemTime,
-    file_handler: RotatingFileHandler,
+    sink: W,
+    rotation_policy: Option<RotationPolicy>,
+    next_rotation_at: Option<SystemTime>,
+    sync_policy: SyncPolicy,
+    max_buffered_bytes: usize,
+    queue: std::collections::VecDeque<Vec<u8>>,
+    queued_bytes: usize,
+    max_queued_records: Option<usize>,
+    drop_policy: DropPolicy,
+    dropped_records: u64,
+    reported_dropped_records: u64,
+    last_flush: SystemTime,
 }
 
-impl RotatingFileMavLogger {
-    /// Creates a new `RotatingFileMavLogger`.
+#[cfg(feature = "std")]
+impl RotatingFileMavLogger<RotatingFileHandler> {
+    /// Creates a new `RotatingFileMavLogger` backed by a `RotatingFileHandler`.
     ///
     /// # Arguments
     ///
@@ -37,6 +182,19 @@ impl RotatingFileMavLogger {
     /// * `backup_count` - The number of backup files to keep.
     /// * `format_flags` - Optional format flags for the log file.
     /// * `mavlink_definitions` - Optional MAVLink message definitions.
+    /// * `rotation_policy` - An optional time-based rotation policy, applied in addition
+    ///   to the `max_bytes` size-based rotation already performed by the file handler.
+    /// * `sync_policy` - An optional flush policy for buffered writes. Defaults to
+    ///   `SyncPolicy::Immediate`, matching the logger's original behavior.
+    /// * `max_buffered_bytes` - An optional "delayed write" size threshold: the buffer
+    ///   is flushed once it exceeds this many bytes regardless of `sync_policy`. Defaults
+    ///   to `DEFAULT_MAX_BUFFERED_BYTES`.
+    /// * `max_queued_records` - An optional high-water mark on the number of records
+    ///   waiting to be flushed. Once reached, `drop_policy` is applied instead of the
+    ///   queue growing further. `None` means unbounded.
+    /// * `drop_policy` - The policy applied once `max_queued_records` is reached.
+    ///   Defaults to `DropPolicy::DropNewest`. Has no effect if `max_queued_records`
+    ///   is `None`.
     ///
     /// # Returns
     ///
@@ -47,6 +205,11 @@ impl RotatingFileMavLogger {
         backup_count: usize,
         format_flags: Option<FormatFlags>,
         mavlink_definitions: Option<MavlinkMessageDefinition>,
+        rotation_policy: Option<RotationPolicy>,
+        sync_policy: Option<SyncPolicy>,
+        max_buffered_bytes: Option<usize>,
+        max_queued_records: Option<usize>,
+        drop_policy: Option<DropPolicy>,
     ) -> std::io::Result<Self> {
         // Handle optional format flags
         let flags: FormatFlags;
@@ -67,15 +230,197 @@ impl RotatingFileMavLogger {
         let file_handler =
             RotatingFileHandler::new(base_path, max_bytes, backup_count, Some(header.pack()))?;
 
+        let now = SystemTime::now();
+        let next_rotation_at = rotation_policy.map(|policy| policy.next_rotation_after(now));
+
         Ok(Self {
             header,
-            time: SystemTime::now(),
-            file_handler,
+            time: now,
+            sink: file_handler,
+            rotation_policy,
+            next_rotation_at,
+            sync_policy: sync_policy.unwrap_or(SyncPolicy::Immediate),
+            max_buffered_bytes: max_buffered_bytes.unwrap_or(DEFAULT_MAX_BUFFERED_BYTES),
+            queue: std::collections::VecDeque::new(),
+            queued_bytes: 0,
+            max_queued_records,
+            drop_policy: drop_policy.unwrap_or(DropPolicy::DropNewest),
+            dropped_records: 0,
+            reported_dropped_records: 0,
+            last_flush: now,
         })
     }
 }
 
-impl MavLogger for RotatingFileMavLogger {
+impl<W: MavLogSink> RotatingFileMavLogger<W> {
+    /// Creates a new `RotatingFileMavLogger` backed by a caller-supplied `MavLogSink`.
+    ///
+    /// This is the entry point for backends other than a desktop rotating file, e.g. an
+    /// embedded FAT filesystem writer or a WASI file descriptor wrapped in `IoWriteSink`.
+    /// The file header is written to `writer` immediately, before any records.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The sink records are framed and written into.
+    /// * `format_flags` - Optional format flags for the log file.
+    /// * `mavlink_definitions` - Optional MAVLink message definitions.
+    /// * `rotation_policy` - An optional time-based rotation policy. With a sink that
+    ///   doesn't override `MavLogSink::rollover`, a boundary writes no new header but
+    ///   still flushes the queue and restarts per-record timestamps from zero.
+    /// * `sync_policy` - An optional flush policy for buffered writes. Defaults to
+    ///   `SyncPolicy::Immediate`.
+    /// * `max_buffered_bytes` - An optional "delayed write" size threshold. Defaults
+    ///   to `DEFAULT_MAX_BUFFERED_BYTES`.
+    /// * `max_queued_records` - An optional high-water mark on the number of records
+    ///   waiting to be flushed. Once reached, `drop_policy` is applied instead of the
+    ///   queue growing further. `None` means unbounded.
+    /// * `drop_policy` - The policy applied once `max_queued_records` is reached.
+    ///   Defaults to `DropPolicy::DropNewest`. Has no effect if `max_queued_records`
+    ///   is `None`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new `RotatingFileMavLogger` or an `io::Error`.
+    pub fn with_writer(
+        mut writer: W,
+        format_flags: Option<FormatFlags>,
+        mavlink_definitions: Option<MavlinkMessageDefinition>,
+        rotation_policy: Option<RotationPolicy>,
+        sync_policy: Option<SyncPolicy>,
+        max_buffered_bytes: Option<usize>,
+        max_queued_records: Option<usize>,
+        drop_policy: Option<DropPolicy>,
+    ) -> std::io::Result<Self> {
+        let flags = format_flags.unwrap_or_default();
+        let msg_definition = mavlink_definitions.unwrap_or_default();
+        let header = FileHeader::new(flags, msg_definition);
+        writer.write_all(&header.pack())?;
+
+        let now = SystemTime::now();
+        let next_rotation_at = rotation_policy.map(|policy| policy.next_rotation_after(now));
+
+        Ok(Self {
+            header,
+            time: now,
+            sink: writer,
+            rotation_policy,
+            next_rotation_at,
+            sync_policy: sync_policy.unwrap_or(SyncPolicy::Immediate),
+            max_buffered_bytes: max_buffered_bytes.unwrap_or(DEFAULT_MAX_BUFFERED_BYTES),
+            queue: std::collections::VecDeque::new(),
+            queued_bytes: 0,
+            max_queued_records,
+            drop_policy: drop_policy.unwrap_or(DropPolicy::DropNewest),
+            dropped_records: 0,
+            reported_dropped_records: 0,
+            last_flush: now,
+        })
+    }
+
+    /// Flushes any queued records to the underlying sink.
+    ///
+    /// This is called automatically according to `sync_policy`, whenever the queue
+    /// exceeds `max_buffered_bytes`, before a rotation, and on `Drop`. Callers using
+    /// `SyncPolicy::EveryN` or `SyncPolicy::Interval` can also call this explicitly to
+    /// force durability at a point of their choosing. Records already written are
+    /// removed from the queue even if a later record in the batch fails to write.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        while let Some(record) = self.queue.pop_front() {
+            self.queued_bytes -= record.len();
+            self.sink.write_all(&record)?;
+        }
+        self.last_flush = SystemTime::now();
+        Ok(())
+    }
+
+    /// Returns the number of records currently queued, waiting to be flushed.
+    pub fn queue_depth(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the total number of records discarded by `drop_policy` since this
+    /// logger was created, because the queue had reached `max_queued_records`.
+    pub fn dropped_record_count(&self) -> u64 {
+        self.dropped_records
+    }
+
+    /// Pushes a framed record onto the queue, applying `drop_policy` if the queue is
+    /// already at `max_queued_records`.
+    fn enqueue(&mut self, record_bytes: Vec<u8>) {
+        if let Some(max_queued_records) = self.max_queued_records {
+            if self.queue.len() >= max_queued_records {
+                match self.drop_policy {
+                    DropPolicy::DropNewest => {
+                        self.dropped_records += 1;
+                        return;
+                    }
+                    DropPolicy::DropOldest => {
+                        if let Some(oldest) = self.queue.pop_front() {
+                            self.queued_bytes -= oldest.len();
+                            self.dropped_records += 1;
+                        }
+                    }
+                }
+            }
+        }
+        self.queued_bytes += record_bytes.len();
+        self.queue.push_back(record_bytes);
+    }
+
+    /// Returns `true` if `sync_policy` (or the `max_buffered_bytes` backstop) calls for
+    /// the queue to be flushed right now.
+    fn should_flush(&self) -> bool {
+        if self.queued_bytes >= self.max_buffered_bytes {
+            return true;
+        }
+        match self.sync_policy {
+            SyncPolicy::Immediate => true,
+            SyncPolicy::EveryN(n) => self.queue.len() >= n,
+            SyncPolicy::Interval(interval) => {
+                self.last_flush.elapsed().map(|e| e >= interval).unwrap_or(true)
+            }
+        }
+    }
+
+    /// Forces a rotation, writing a fresh `FileHeader` to the new destination and
+    /// resetting per-record relative timestamps so they stay meaningful.
+    ///
+    /// This is called automatically from `write()` once `next_rotation_at` has passed,
+    /// independently of whether `max_bytes` has been reached.
+    fn rotate_for_time_boundary(&mut self) -> std::io::Result<()> {
+        // Flush any buffered records to the current destination before rolling over.
+        self.flush()?;
+
+        let new_header = FileHeader::new(
+            FormatFlags {
+                mavlink_only: self.header.format_flags.mavlink_only,
+                not_timestamped: self.header.format_flags.not_timestamped,
+                compression: self.header.format_flags.compression,
+            },
+            MavlinkMessageDefinition {
+                version_major: self.header.message_definition.version_major,
+                version_minor: self.header.message_definition.version_minor,
+                dialect: self.header.message_definition.dialect.clone(),
+                payload_type: self.header.message_definition.payload_type,
+                size: self.header.message_definition.size,
+                payload: self.header.message_definition.payload.clone(),
+            },
+        );
+
+        self.sink.rollover(Some(&new_header.pack()))?;
+        self.header = new_header;
+
+        let now = SystemTime::now();
+        self.time = now;
+        self.next_rotation_at = self
+            .rotation_policy
+            .map(|policy| policy.next_rotation_after(now));
+
+        Ok(())
+    }
+}
+
+impl<W: MavLogSink> MavLogger for RotatingFileMavLogger<W> {
     /// Writes a MAVLink message to the log.
     ///
     /// # Arguments
@@ -101,7 +446,7 @@ impl MavLogger for RotatingFileMavLogger {
     }
 }
 
-impl RotatingFileMavLogger {
+impl<W: MavLogSink> RotatingFileMavLogger<W> {
     /// Writes a text message to the log.
     ///
     /// # Arguments
@@ -129,6 +474,47 @@ impl RotatingFileMavLogger {
         self.write(EntryType::Raw, data)
     }
 
+    /// Frames a single record (type byte, timestamp, size/compression fields, and
+    /// payload) according to the header's `FormatFlags`, without touching the queue.
+    fn frame_record(&mut self, entry_type: EntryType, data: &[u8]) -> Vec<u8> {
+        let mut record_bytes: Vec<u8> = Vec::new();
+        if !self.header.format_flags.mavlink_only {
+            // If mavlink only, there is no need to track the entry type
+            record_bytes.extend_from_slice(&(entry_type as u8).to_le_bytes());
+        }
+        if !self.header.format_flags.not_timestamped {
+            // If tracking log entry time, add the timestamp
+            let timestamp_us: u64 = match self.time.elapsed() {
+                Ok(elapsed) => elapsed.as_micros() as u64,
+                Err(_) => {
+                    self.time = SystemTime::now();
+                    0
+                }
+            };
+            record_bytes.extend_from_slice(&timestamp_us.to_le_bytes());
+        }
+        // If mavlink only, there is no size field (frames are self-delimited by their own
+        // v1/v2 header and CRC), so compression is not applied in that mode.
+        let payload: Vec<u8> = if self.header.format_flags.mavlink_only {
+            data.to_vec()
+        } else if let Some(compression) = self.header.format_flags.compression {
+            let compressed = compression.compress(data);
+            // Only store compressed if it actually shrinks the payload, so tiny
+            // records that don't compress well aren't stored larger than the original.
+            let is_compressed = compressed.len() < data.len();
+            let body = if is_compressed { compressed } else { data.to_vec() };
+            record_bytes.extend_from_slice(&(body.len() as u16).to_le_bytes());
+            record_bytes.push(is_compressed as u8);
+            body
+        } else {
+            let size: u16 = data.len() as u16;
+            record_bytes.extend_from_slice(&size.to_le_bytes());
+            data.to_vec()
+        };
+        record_bytes.extend_from_slice(&payload);
+        record_bytes
+    }
+
     /// Writes a log entry to the file.
     ///
     /// # Arguments
@@ -140,6 +526,14 @@ impl RotatingFileMavLogger {
     ///
     /// A `Result` indicating success or failure.
     fn write(&mut self, entry_type: EntryType, data: &[u8]) -> std::io::Result<()> {
+        // Force a time-boundary rotation before writing, even if the file is under
+        // max_bytes, so idle vehicles still roll logs at the expected wall-clock boundary.
+        if let Some(next_rotation_at) = self.next_rotation_at {
+            if SystemTime::now() >= next_rotation_at {
+                self.rotate_for_time_boundary()?;
+            }
+        }
+
         // If we are in MAVLink only mode and there is an attempt to write a non MAVLink entry, return an error.
         if entry_type != EntryType::Mavlink && self.header.format_flags.mavlink_only {
             return Err(std::io::Error::new(
@@ -148,35 +542,36 @@ impl RotatingFileMavLogger {
             ));
         }
 
-        // Construct the log entry
-        let mut record_bytes: Vec<u8> = Vec::new();
-        if !self.header.format_flags.mavlink_only {
-            // If mavlink only, there is no need to track the entry type
-            record_bytes.extend_from_slice(&(entry_type as u8).to_le_bytes());
+        // Surface any records dropped since the last report as a synthetic text marker,
+        // so a reader can tell a gap in the log was intentional overload shedding rather
+        // than corruption. Mavlink-only mode has no room for non-mavlink entries, so the
+        // drop count simply accumulates silently in that mode.
+        let unreported = self.dropped_records - self.reported_dropped_records;
+        if unreported > 0 && !self.header.format_flags.mavlink_only {
+            let marker = format!("{unreported} entries dropped");
+            let marker_bytes = self.frame_record(EntryType::Text, marker.as_bytes());
+            self.enqueue(marker_bytes);
+            self.reported_dropped_records = self.dropped_records;
         }
-        if !self.header.format_flags.not_timestamped {
-            // If tracking log entry time, add the timestamp
-            let timestamp_us: u64 = match self.time.elapsed() {
-                Ok(elapsed) => elapsed.as_micros() as u64,
-                Err(_) => {
-                    self.time = SystemTime::now();
-                    0
-                }
-            };
-            record_bytes.extend_from_slice(&timestamp_us.to_le_bytes());
-        }
-        if !self.header.format_flags.mavlink_only {
-            // If mavlink only, no need to add the payload size
-            let size: u16 = data.len() as u16;
-            record_bytes.extend_from_slice(&size.to_le_bytes());
+
+        let record_bytes = self.frame_record(entry_type, data);
+        self.enqueue(record_bytes);
+
+        if self.should_flush() {
+            self.flush()?;
         }
-        record_bytes.extend_from_slice(data);
-        self.file_handler.emit(&record_bytes)?;
 
         Ok(())
     }
 }
 
+impl<W: MavLogSink> Drop for RotatingFileMavLogger<W> {
+    /// Flushes any buffered records so that a dropped logger never silently loses data.
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mavlink::MavHeader;
@@ -232,7 +627,7 @@ mod tests {
 
         // Create a new logger instance
         let mut logger: RotatingFileMavLogger =
-            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, None, None)
+            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, None, None, None, None, None, None, None)
                 .expect("Failed to create logger");
 
         // Populate the log file
@@ -346,7 +741,7 @@ mod tests {
 
         // Create a new logger instance with the format flags
         let mut logger: RotatingFileMavLogger =
-            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, Some(format_flags), None)
+            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, Some(format_flags), None, None, None, None, None, None)
                 .expect("Failed to create logger");
 
         // Populate the log file
@@ -441,7 +836,7 @@ mod tests {
 
         // Create a new logger instance with the format flags
         let mut logger: RotatingFileMavLogger =
-            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, Some(format_flags), None)
+            RotatingFileMavLogger::new(CASE_FILE_NAME, 1000, 0, Some(format_flags), None, None, None, None, None, None)
                 .expect("Failed to create logger");
 
         populate_log_file(&mut logger);
@@ -470,4 +865,175 @@ mod tests {
         // Clean up the test file
         std::fs::remove_file(CASE_FILE_NAME).unwrap();
     }
+
+    #[test]
+    /// Tests that `RotationPolicy` computes the expected next rotation instant.
+    fn test_rotation_policy_next_rotation_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(10);
+        let next = RotationPolicy::Interval(Duration::from_secs(5)).next_rotation_after(now);
+        assert_eq!(next, UNIX_EPOCH + Duration::from_secs(15));
+
+        // 3600 * 2 + 10 seconds into the epoch should roll to the next hour boundary.
+        let now = UNIX_EPOCH + Duration::from_secs(3600 * 2 + 10);
+        let next = RotationPolicy::Hourly.next_rotation_after(now);
+        assert_eq!(next, UNIX_EPOCH + Duration::from_secs(3600 * 3));
+
+        // 86400 * 2 + 10 seconds into the epoch should roll to the next day boundary.
+        let now = UNIX_EPOCH + Duration::from_secs(86400 * 2 + 10);
+        let next = RotationPolicy::Daily.next_rotation_after(now);
+        assert_eq!(next, UNIX_EPOCH + Duration::from_secs(86400 * 3));
+    }
+
+    #[test]
+    /// Tests that `SyncPolicy::EveryN` defers writes to the file until the batch fills,
+    /// and that an explicit `flush()` makes a partial batch visible on disk.
+    fn test_sync_policy_every_n_buffers_until_flush() {
+        const CASE_FILE_NAME: &str = "test_sync_policy_every_n.mav";
+        std::fs::remove_file(CASE_FILE_NAME).unwrap_or_else(|_| {});
+
+        let mut logger = RotatingFileMavLogger::new(
+            CASE_FILE_NAME,
+            10_000,
+            0,
+            None,
+            None,
+            None,
+            Some(SyncPolicy::EveryN(3)),
+            None,
+            None,
+            None,
+        )
+        .expect("Failed to create logger");
+
+        logger.write_text("a").unwrap();
+        logger.write_text("b").unwrap();
+
+        // Only two of three records buffered: nothing should be on disk yet.
+        let content_before = std::fs::read(CASE_FILE_NAME).unwrap();
+        assert_eq!(content_before.len(), FileHeader::MIN_SIZE);
+
+        logger.flush().unwrap();
+        let content_after_flush = std::fs::read(CASE_FILE_NAME).unwrap();
+        assert!(content_after_flush.len() > content_before.len());
+
+        // The third record reaches the EveryN threshold and flushes on its own.
+        logger.write_text("c").unwrap();
+        logger.write_text("d").unwrap();
+        logger.write_text("e").unwrap();
+        let content_after_batch = std::fs::read(CASE_FILE_NAME).unwrap();
+        assert!(content_after_batch.len() > content_after_flush.len());
+
+        drop(logger);
+        std::fs::remove_file(CASE_FILE_NAME).unwrap();
+    }
+
+    /// A `MavLogSink` backed by a shared buffer, standing in for a `no_std` embedded
+    /// destination (e.g. an SD card writer) that isn't backed by `std::io::Write`.
+    #[derive(Clone)]
+    struct SharedBufferSink(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl MavLogSink for SharedBufferSink {
+        fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    #[test]
+    /// Tests that `with_writer` drives the same record framing against a custom
+    /// `MavLogSink`, without going through `RotatingFileHandler` at all.
+    fn test_with_writer_generic_sink() {
+        let buffer = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut logger = RotatingFileMavLogger::with_writer(
+            SharedBufferSink(buffer.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("Failed to create logger");
+
+        logger.write_text("hi").unwrap();
+        logger.flush().unwrap();
+
+        let content = buffer.borrow();
+        assert_eq!(content.len(), FileHeader::MIN_SIZE + 1 + 8 + 2 + 2);
+        assert_eq!(content[FileHeader::MIN_SIZE], EntryType::Text as u8);
+        assert_eq!(
+            content[FileHeader::MIN_SIZE + 9..FileHeader::MIN_SIZE + 11],
+            [2, 0]
+        ); // payload size
+        assert_eq!(&content[FileHeader::MIN_SIZE + 11..], b"hi");
+    }
+
+    #[test]
+    /// Tests that `DropPolicy::DropOldest` discards the oldest queued record once
+    /// `max_queued_records` is reached, and that the drop count is surfaced as a
+    /// synthetic text marker the next time a record is written.
+    fn test_drop_policy_drop_oldest_reports_dropped_records() {
+        use super::super::reader::{LogEntry, RotatingFileMavReader};
+
+        const CASE_FILE_NAME: &str = "test_drop_policy_drop_oldest.mav";
+        std::fs::remove_file(CASE_FILE_NAME).unwrap_or_else(|_| {});
+
+        let mut logger = RotatingFileMavLogger::new(
+            CASE_FILE_NAME,
+            10_000,
+            0,
+            None,
+            None,
+            None,
+            Some(SyncPolicy::EveryN(usize::MAX)),
+            None,
+            Some(2),
+            Some(DropPolicy::DropOldest),
+        )
+        .expect("Failed to create logger");
+
+        logger.write_text("a").unwrap();
+        logger.write_text("b").unwrap();
+        assert_eq!(logger.queue_depth(), 2);
+
+        // The queue is already full, so this write drops the oldest ("a").
+        logger.write_text("c").unwrap();
+        assert_eq!(logger.queue_depth(), 2);
+        assert_eq!(logger.dropped_record_count(), 1);
+
+        logger.flush().unwrap();
+
+        // The next write, into a now-empty queue, surfaces the drop as a synthetic
+        // text marker ahead of itself.
+        logger.write_text("d").unwrap();
+        logger.flush().unwrap();
+        drop(logger);
+
+        let mut reader = RotatingFileMavReader::<mavlink::common::MavMessage>::new(CASE_FILE_NAME)
+            .expect("Failed to open log for reading");
+        let first = reader.next().unwrap().unwrap();
+        match first.entry {
+            LogEntry::Text(text) => assert_eq!(text, "b"),
+            _ => panic!("expected a text entry"),
+        }
+        let second = reader.next().unwrap().unwrap();
+        match second.entry {
+            LogEntry::Text(text) => assert_eq!(text, "c"),
+            _ => panic!("expected a text entry"),
+        }
+        let third = reader.next().unwrap().unwrap();
+        match third.entry {
+            LogEntry::Text(text) => assert_eq!(text, "1 entries dropped"),
+            _ => panic!("expected the dropped-records marker"),
+        }
+        let fourth = reader.next().unwrap().unwrap();
+        match fourth.entry {
+            LogEntry::Text(text) => assert_eq!(text, "d"),
+            _ => panic!("expected a text entry"),
+        }
+        assert!(reader.next().is_none());
+
+        std::fs::remove_file(CASE_FILE_NAME).unwrap();
+    }
 }
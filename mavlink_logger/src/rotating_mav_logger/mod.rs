@@ -0,0 +1,19 @@
+//! This module implements a rotating-file MAVLink logger and its matching
+//! reader, along with the on-disk `FileHeader` format they share.
+
+mod compression;
+mod header;
+mod logger;
+mod reader;
+
+pub use compression::Compression;
+pub use header::{
+    FileHeader, FormatFlags, MavlinkDefinitionPayloadType, MavlinkMessageDefinition,
+};
+#[cfg(feature = "std")]
+pub use logger::IoWriteSink;
+pub use logger::{
+    DropPolicy, MavLogSink, RotatingFileMavLogger, RotationPolicy, SyncPolicy,
+    DEFAULT_MAX_BUFFERED_BYTES,
+};
+pub use reader::{LogEntry, LogRecord, RotatingFileMavReader};